@@ -8,11 +8,21 @@ use std::{
 use yew::{html::ImplicitClone, AttrValue};
 
 use crate::{
-    model::{Model, ModelState, State},
-    modifier::Modifier,
+    diff::FieldChange,
+    field::Field,
+    format::{MessageFormatter, SharedFormatter},
+    history::History,
+    hooks::UseGenerationHandle,
+    model::{
+        EnumModel, Flatten, Model, ModelMessages, ModelState, State, Touched, Validating, Value,
+        Visit, Visitor,
+    },
+    modifier::{Modifier, VariantModifier},
     state_model::{
-        MappedOptionStateModel, MappedStateModel, MappedVecStateModel, Mapping, StateModelRc,
+        MappedOptionStateModel, MappedStateModel, MappedVariantStateModel, MappedVecStateModel,
+        Mapping, StateModelRc, VariantMapping,
     },
+    validation::ValidationErrors,
 };
 
 /// Use to bind between a [`Model`] or [`Value`](crate::model::Value) and a component
@@ -24,6 +34,9 @@ where
     state_model: StateModelRc<T>,
     name: AttrValue,
     valid_generation: usize,
+    generation_handle: UseGenerationHandle,
+    formatter: Option<SharedFormatter>,
+    history: Option<Rc<dyn History>>,
 }
 
 impl<T: Debug> Debug for Binding<T>
@@ -47,22 +60,40 @@ where
     pub(crate) fn new(
         state_model: StateModelRc<T>,
         name: impl Into<AttrValue>,
-        valid_generation: usize,
+        generation_handle: UseGenerationHandle,
     ) -> Self {
         Self {
             state_model,
             name: name.into(),
-            valid_generation,
+            valid_generation: generation_handle.generation(),
+            generation_handle,
+            formatter: None,
+            history: None,
+        }
+    }
+
+    /// Creates a binding to a child of the model, sharing the generation of this binding
+    fn child<M>(&self, state_model: StateModelRc<M>, name: impl Into<AttrValue>) -> Binding<M>
+    where
+        M: ModelState,
+    {
+        Binding {
+            state_model,
+            name: name.into(),
+            valid_generation: self.valid_generation,
+            generation_handle: self.generation_handle.clone(),
+            formatter: self.formatter.clone(),
+            history: self.history.clone(),
         }
     }
 
     /// Gets the [`Model`] or [`Value`](crate::model::Value) the binding is bound to
-    pub fn model(&self) -> Ref<T> {
+    pub fn model(&self) -> Ref<'_, T> {
         self.state_model.model()
     }
 
     /// Gets the current state of the [`Model`] or [`Value`](crate::model::Value) the binding is bound to
-    pub fn state(&self) -> Ref<T::State> {
+    pub fn state(&self) -> Ref<'_, T::State> {
         self.state_model.state()
     }
 
@@ -71,6 +102,140 @@ where
         Modifier::create(self.state_model.clone())
     }
 
+    /// Validates the whole model, showing the messages of all its fields regardless of whether they are dirty.
+    /// All fields are marked as touched.
+    /// Asynchronous validators are started for fields that were not checked yet,
+    /// while they are pending an error with code [`VALIDATING_CODE`](crate::field::VALIDATING_CODE) is reported for their field.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        self.state_model.as_mut().1.touch();
+        self.modifier().validate(true)
+    }
+
+    /// Validates the whole model like [`validate()`](Self::validate), and returns a copy of the model when valid
+    pub fn submit(&self) -> Result<T, ValidationErrors>
+    where
+        T: Clone,
+    {
+        self.validate()?;
+        Ok(self.model().clone())
+    }
+
+    /// Rebuilds the model from the initial values kept by its state, including items that were removed from lists.
+    /// Parts of the model that aren't bound, like skipped fields, have their current value.
+    pub fn initial_model(&self) -> T
+    where
+        T: Clone,
+    {
+        let mut model = self.model().clone();
+        State::<T>::restore_initial(&*self.state(), &mut model);
+        model
+    }
+
+    /// Walks the state of the model with `visitor`, starting at the binding's name.
+    /// A closure taking the path and [`Field`] of every field can be used as visitor.
+    pub fn visit(&self, visitor: &mut dyn Visitor) {
+        self.state().visit(&self.name, visitor);
+    }
+
+    /// Returns the validation messages of the model by the binding names of their fields, in the order of the model.
+    /// Messages of the model as a whole, e.g. from `#[validate(schema(..))]` functions, are listed under the name of the model.
+    /// Messages are formatted by the binding's formatter or else by the [`EnglishFormatter`](crate::format::EnglishFormatter).
+    pub fn messages_by_path(&self) -> Vec<(String, AttrValue)> {
+        struct Collect<'a>(&'a dyn MessageFormatter, Vec<(String, AttrValue)>);
+
+        impl Visitor for Collect<'_> {
+            fn field(&mut self, path: &str, field: &Field) {
+                if let Some(message) = field.format_message(self.0) {
+                    self.1.push((path.to_owned(), message));
+                }
+            }
+
+            fn messages(&mut self, path: &str, messages: &crate::model::Messages) {
+                let messages = messages.format(self.0);
+                self.1.extend(
+                    messages
+                        .into_iter()
+                        .map(|message| (path.to_owned(), message)),
+                );
+            }
+        }
+
+        let formatter = self.formatter.clone().unwrap_or_default();
+        let mut messages = Collect(&*formatter, Vec::new());
+        self.visit(&mut messages);
+        messages.1
+    }
+
+    /// Returns the errors of raw input that could not be parsed by the binding names of their fields, in the order of the model.
+    /// Errors are formatted by the binding's formatter or else by the [`EnglishFormatter`](crate::format::EnglishFormatter).
+    pub fn errors_by_path(&self) -> Vec<(String, AttrValue)> {
+        let formatter = self.formatter.clone().unwrap_or_default();
+        let mut errors = Vec::new();
+        self.visit(&mut |path: &str, field: &Field| {
+            if let Some(error) = field.format_error(&*formatter) {
+                errors.push((path.to_owned(), error));
+            }
+        });
+        errors
+    }
+
+    /// Returns the fields that changed relative to their initial values, see [`changes()`](crate::diff::changes)
+    pub fn changes(&self) -> Vec<FieldChange> {
+        crate::diff::changes(&*self.state(), &self.name)
+    }
+
+    /// Returns the [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396) merge patch of the model relative to its [initial model](Self::initial_model)
+    #[cfg(feature = "serde_json")]
+    pub fn merge_patch(&self) -> serde_json::Result<serde_json::Value>
+    where
+        T: Clone + serde::Serialize,
+    {
+        let initial = serde_json::to_value(self.initial_model())?;
+        let current = serde_json::to_value(&*self.model())?;
+        Ok(crate::diff::merge_patch(&initial, &current))
+    }
+
+    /// Returns the [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) patch of the model relative to its [initial model](Self::initial_model)
+    #[cfg(feature = "serde_json")]
+    pub fn json_patch(&self) -> serde_json::Result<serde_json::Value>
+    where
+        T: Clone + serde::Serialize,
+    {
+        let initial = serde_json::to_value(self.initial_model())?;
+        let current = serde_json::to_value(&*self.model())?;
+        Ok(crate::diff::json_patch(&initial, &current))
+    }
+
+    /// Replaces the model and its state, e.g. with a form session stored through the `serde` feature.
+    /// The state is attached to this binding, so its changes rerender the component again.
+    pub fn restore(&self, model: T, mut state: T::State) {
+        // The state takes the next generation, so bindings of the previous state are outdated
+        self.generation_handle.increase();
+        state.attach(self.generation_handle.clone());
+
+        let (mut model_mut, mut state_mut) = self.state_model.as_mut();
+        *model_mut = model;
+        *state_mut = state;
+    }
+
+    /// Resolves once none of the asynchronous validators of the model are pending
+    pub async fn wait_validation(&self) {
+        while self.state().validating() {
+            self.generation_handle.changed().await;
+        }
+    }
+
+    /// Like [`submit()`](Self::submit), but waits for the asynchronous validators of the model to complete first
+    pub async fn submit_async(&self) -> Result<T, ValidationErrors>
+    where
+        T: Clone,
+    {
+        // Starts the asynchronous validators of all fields, the result is known after they completed
+        let _ = self.validate();
+        self.wait_validation().await;
+        self.submit()
+    }
+
     /// The name of the binding, each subsequent binding will append to the root name.
     /// A snake_case name is automatically provided for structs deriving [`Model`].
     /// For [`Value`](crate::model::Value)'s a name has to be provided upon binding with [`use_named_binding()`](fn@crate::hooks::use_named_binding)
@@ -117,12 +282,61 @@ where
     /// }
     /// ```
     pub fn name_reset(&self, name: impl Into<AttrValue>) -> Self {
+        self.child(self.state_model.clone(), name)
+    }
+
+    /// Sets the formatter for the messages of the model, it applies to all bindings mapped from the returned binding.
+    /// Components fall back to the formatter provided through context when none is set.
+    pub fn with_formatter(&self, formatter: impl Into<SharedFormatter>) -> Self {
         Self {
-            state_model: self.state_model.clone(),
-            name: name.into(),
-            valid_generation: self.valid_generation,
+            formatter: Some(formatter.into()),
+            ..self.clone()
         }
     }
+
+    /// Returns the formatter set through [`with_formatter()`](Self::with_formatter)
+    pub fn formatter(&self) -> Option<&SharedFormatter> {
+        self.formatter.as_ref()
+    }
+
+    pub(crate) fn with_history(mut self, history: Rc<dyn History>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Reverts the latest step of the history of the whole model, returns whether there was a step to undo.
+    /// Only bindings created by [`use_binding_with_history()`](fn@crate::hooks::use_binding_with_history), and the bindings mapped from them, have a history.
+    pub fn undo(&self) -> bool {
+        self.history.clone().is_some_and(History::undo)
+    }
+
+    /// Applies the latest step reverted by [`undo()`](Self::undo) again, returns whether there was a step to redo
+    pub fn redo(&self) -> bool {
+        self.history.clone().is_some_and(History::redo)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| history.can_undo())
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| history.can_redo())
+    }
+
+    #[doc(hidden)]
+    /// Maps the binding to a model embedded through `#[yfb(flatten)]`, the name of the binding is kept as is
+    /// Prefer using the generated field_binding() methods instead.
+    pub fn flatten<M, I>(&self) -> Binding<M>
+    where
+        T: Flatten<M, I>,
+        M: ModelState,
+    {
+        self.child(T::flatten(self.state_model.clone()), self.name.clone())
+    }
 }
 
 impl<T> Binding<T>
@@ -137,24 +351,80 @@ where
         M: Mapping<From = T>,
     {
         let name: AttrValue = format!("{}.{}", self.name, M::NAME).into();
-        Binding::new(
+        self.child(
             Rc::new(MappedStateModel::new(self.state_model.clone(), mapping)),
             name,
-            self.valid_generation,
         )
     }
 }
 
+impl<T> Binding<T>
+where
+    T: EnumModel + Model,
+    T::Modifier: VariantModifier<T>,
+{
+    #[doc(hidden)]
+    /// Maps the binding to a field of one of the variants through a [`VariantMapping`]
+    /// Prefer using the generated variant_field_binding() methods instead.
+    pub fn map_variant<M>(&self, mapping: M) -> Binding<M::To>
+    where
+        M: VariantMapping<From = T>,
+    {
+        let name: AttrValue = format!("{}.{}", self.name, M::NAME).into();
+        self.child(
+            Rc::new(MappedVariantStateModel::new(
+                self.state_model.clone(),
+                mapping,
+            )),
+            name,
+        )
+    }
+
+    /// Returns the currently active variant of the model
+    pub fn variant(&self) -> T::Variant {
+        self.model().variant()
+    }
+
+    /// Switches the model to `variant`, see [`VariantModifier::switch_variant()`]
+    pub fn switch_variant(&self, variant: T::Variant) {
+        self.modifier().switch_variant(variant);
+    }
+}
+
+impl<T> Binding<T>
+where
+    T: ModelState,
+    T::State: ModelMessages,
+{
+    /// Returns the messages of errors that apply to the model as a whole rather than to one of its fields,
+    /// e.g. from `#[validate(schema(..))]` functions.
+    /// Messages are formatted by the binding's formatter or else by the [`EnglishFormatter`](crate::format::EnglishFormatter).
+    pub fn messages(&self) -> Vec<AttrValue> {
+        let formatter = self.formatter.clone().unwrap_or_default();
+        self.state().messages().format(&*formatter)
+    }
+}
+
+impl<T> Binding<T>
+where
+    T: Value,
+{
+    /// Returns the message of the field, formatted by the binding's formatter or else by the [`EnglishFormatter`](crate::format::EnglishFormatter)
+    pub fn message(&self) -> Option<AttrValue> {
+        let formatter = self.formatter.clone().unwrap_or_default();
+        self.state().format_message(&*formatter)
+    }
+}
+
 impl<T> Binding<Option<T>>
 where
     T: ModelState + Default,
 {
     /// Maps a `Binding<Option<T>>` to a `Binding<T>`
     pub fn map_option(&self) -> Binding<T> {
-        Binding::new(
+        self.child(
             Rc::new(MappedOptionStateModel::new(self.state_model.clone())),
             self.name.clone(),
-            self.valid_generation,
         )
     }
 }
@@ -166,10 +436,9 @@ where
     /// Maps a `Binding<Vec<T>>` to a `Binding<T>` with the corresponding `index`
     pub fn map_item(&self, index: usize) -> Binding<T> {
         let name: AttrValue = format!("{}[{}]", self.name, index).into();
-        Binding::new(
+        self.child(
             Rc::new(MappedVecStateModel::new(self.state_model.clone(), index)),
             name,
-            self.valid_generation,
         )
     }
 }
@@ -187,6 +456,7 @@ where
 
         max_generation <= self.valid_generation.min(other.valid_generation)
             && self.name == other.name
+            && self.formatter == other.formatter
     }
 }
 
@@ -195,11 +465,7 @@ where
     T: ModelState,
 {
     fn clone(&self) -> Self {
-        Self {
-            state_model: self.state_model.clone(),
-            name: self.name.clone(),
-            valid_generation: self.valid_generation,
-        }
+        self.child(self.state_model.clone(), self.name.clone())
     }
 }
 
@@ -390,7 +656,7 @@ mod tests {
 
         binding.modifier().a().set("42");
         assert_eq!(
-            binding.state().a.message().map(AttrValue::as_str),
+            binding.state().a.message().as_deref(),
             Some("Not within range")
         );
         assert_eq!(binding.state().b.message(), None);
@@ -398,4 +664,1590 @@ mod tests {
         assert_eq!(binding.state().a.message(), None);
         assert_eq!(binding.state().b.message(), None);
     }
+
+    #[test]
+    fn test_enum_variant() {
+        #[derive(Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            payment: Payment,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Payment {
+            Card { number: String, cvc: u16 },
+            Iban { iban: String },
+            Cash,
+        }
+
+        let binding = use_binding(|| Order {
+            payment: Payment::Card {
+                number: "1234".into(),
+                cvc: 123,
+            },
+        });
+        let payment = binding.payment_binding();
+
+        assert_eq!(payment.variant(), PaymentVariant::Card);
+        assert_eq!(
+            payment.card_number_binding().name(),
+            "order.payment.card.number"
+        );
+        assert_eq!(*payment.card_number_binding().model(), "1234");
+        assert_eq!(*payment.iban_iban_binding().model(), "");
+        assert!(!binding.state().dirty());
+
+        payment.switch_variant(PaymentVariant::Iban);
+        assert_eq!(payment.variant(), PaymentVariant::Iban);
+        assert_eq!(payment.state().variant(), PaymentVariant::Iban);
+        assert_eq!(
+            *binding.model(),
+            Order {
+                payment: Payment::Iban { iban: "".into() }
+            }
+        );
+        assert!(binding.state().dirty());
+
+        // Modifying a field of an inactive variant switches to that variant
+        payment.modifier().card_number().set("1234");
+        assert_eq!(payment.variant(), PaymentVariant::Card);
+        assert_eq!(
+            *binding.model(),
+            Order {
+                payment: Payment::Card {
+                    number: "1234".into(),
+                    cvc: 0,
+                }
+            }
+        );
+        assert!(payment.state().card_cvc.dirty());
+        assert!(binding.state().dirty());
+
+        payment.card_cvc_binding().modifier().set("123");
+        assert!(!binding.state().dirty());
+
+        payment.switch_variant(PaymentVariant::Cash);
+        assert_eq!(*payment.model(), Payment::Cash);
+        assert!(binding.state().dirty());
+
+        payment.modifier().replace_model(Payment::Cash);
+        assert!(!binding.state().dirty());
+    }
+
+    #[test]
+    fn test_enum_field_names() {
+        // Fields of the variants don't collide with the fields the state keeps for the enum itself
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Source {
+            Generation { handle: String },
+            Initial { variant: u32 },
+        }
+
+        let binding = use_binding(|| Source::Generation { handle: "a".into() });
+        binding.generation_handle_binding().modifier().set("b");
+        assert_eq!(binding.state().variant(), SourceVariant::Generation);
+        assert_eq!(*binding.state().generation_handle.value(), "b");
+        assert!(binding.state().dirty());
+
+        binding.initial_variant_binding().modifier().set("1");
+        assert_eq!(binding.state().variant(), SourceVariant::Initial);
+        assert_eq!(*binding.model(), Source::Initial { variant: 1 });
+    }
+
+    #[test]
+    fn test_enum_errors() {
+        use crate::{
+            model::Validator,
+            validation::{FieldError, ValidationErrors, SCHEMA_KEY},
+        };
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate")]
+        struct Checkout {
+            payment: Payment,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Payment {
+            Card { number: String },
+            Cash,
+        }
+
+        impl Validator for Checkout {
+            fn validate(&self) -> Result<(), ValidationErrors> {
+                let Payment::Card { number } = &self.payment else {
+                    return Ok(());
+                };
+                let mut payment = ValidationErrors::new();
+                if number.len() < 4 {
+                    payment.add("number", FieldError::new("too_short"));
+                }
+                payment.add(SCHEMA_KEY, FieldError::new("declined"));
+                let mut errors = ValidationErrors::new();
+                errors.add_nested("payment", payment);
+                Err(errors)
+            }
+        }
+
+        let binding = use_binding(|| Checkout {
+            payment: Payment::Card {
+                number: "1234".into(),
+            },
+        });
+        binding.modifier().payment().card_number().set("12");
+        assert_eq!(
+            binding.state().payment.card_number.message().as_deref(),
+            Some("too_short")
+        );
+        assert_eq!(binding.payment_binding().messages(), ["declined"]);
+
+        binding
+            .modifier()
+            .payment()
+            .switch_variant(PaymentVariant::Cash);
+        assert!(binding.payment_binding().messages().is_empty());
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        #[derive(Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Route {
+            distance: Meters,
+            stop: Pair,
+            via: Via,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        struct Meters(f64);
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        struct Pair(String, u32);
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Via {
+            Road(String),
+            Direct,
+        }
+
+        let binding = use_binding(|| Route {
+            distance: Meters(1.5),
+            stop: Pair("a".into(), 1),
+            via: Via::Direct,
+        });
+
+        assert_eq!(
+            binding.distance_binding()._0_binding().name(),
+            "route.distance.0"
+        );
+        assert_eq!(binding.stop_binding()._1_binding().name(), "route.stop.1");
+        assert_eq!(
+            binding.via_binding().road_0_binding().name(),
+            "route.via.road.0"
+        );
+        assert_eq!(binding.state().distance.0.value(), "1.5");
+
+        binding.modifier().distance()._0().set("2");
+        binding.stop_binding().modifier()._0().set("b");
+        binding.via_binding().road_0_binding().modifier().set("c");
+        assert_eq!(
+            *binding.model(),
+            Route {
+                distance: Meters(2.0),
+                stop: Pair("b".into(), 1),
+                via: Via::Road("c".into()),
+            }
+        );
+        assert!(binding.state().stop.0.dirty());
+        assert!(!binding.state().stop.1.dirty());
+    }
+
+    #[test]
+    fn test_no_dirty_validation() {
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Note {
+            #[yfb(no_dirty)]
+            #[validate(length(min = 3, message = "Too short"))]
+            draft: String,
+        }
+
+        let binding = use_binding(Note::default);
+        binding.modifier().draft().set("a");
+        assert!(!binding.state().dirty());
+        assert_eq!(
+            binding.state().draft.message().as_deref(),
+            Some("Too short")
+        );
+
+        binding.modifier().draft().set("abc");
+        assert_eq!(binding.state().draft.message(), None);
+    }
+
+    #[test]
+    fn test_skip_and_no_dirty() {
+        #[derive(Debug, PartialEq)]
+        struct Handle;
+
+        #[derive(Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Document {
+            #[yfb(skip)]
+            handle: Handle,
+            title: String,
+            #[yfb(no_dirty)]
+            draft: String,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Attachment {
+            File {
+                #[yfb(skip)]
+                handle: Option<std::rc::Rc<()>>,
+                name: String,
+            },
+        }
+
+        let binding = use_binding(|| Document {
+            handle: Handle,
+            title: "title".into(),
+            draft: "".into(),
+        });
+
+        binding.draft_binding().modifier().set("draft");
+        assert!(binding.state().draft.dirty());
+        assert!(!binding.state().dirty());
+
+        binding.title_binding().modifier().set("changed");
+        assert!(binding.state().dirty());
+        assert_eq!(binding.model().handle, Handle);
+
+        let binding = use_named_binding("attachment", || Attachment::File {
+            handle: Some(Default::default()),
+            name: "a".into(),
+        });
+        binding.file_name_binding().modifier().set("b");
+        assert!(matches!(
+            &*binding.model(),
+            Attachment::File { handle: Some(_), name } if name == "b"
+        ));
+    }
+
+    #[test]
+    fn test_rename() {
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate", rename_all = "camelCase")]
+        struct UserProfile {
+            first_name: String,
+            #[yfb(rename = "surname")]
+            last_name: String,
+            home_address: Address,
+        }
+
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate", rename = "addr")]
+        struct Address {
+            street_name: String,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate, rename_all = "kebab-case")]
+        enum Contact {
+            PhoneNumber {
+                country_code: u16,
+            },
+            #[yfb(rename = "mail")]
+            EmailAddress(String),
+        }
+
+        let binding = use_binding(UserProfile::default);
+        assert_eq!(binding.name(), "user_profile");
+        assert_eq!(
+            binding.first_name_binding().name(),
+            "user_profile.firstName"
+        );
+        assert_eq!(binding.last_name_binding().name(), "user_profile.surname");
+        assert_eq!(
+            binding.home_address_binding().street_name_binding().name(),
+            "user_profile.homeAddress.street_name"
+        );
+        assert_eq!(use_binding(Address::default).name(), "addr");
+
+        let binding = use_binding(|| Contact::EmailAddress("".into()));
+        assert_eq!(binding.name(), "contact");
+        assert_eq!(
+            binding.phone_number_country_code_binding().name(),
+            "contact.phone-number.country-code"
+        );
+        assert_eq!(binding.email_address_0_binding().name(), "contact.mail.0");
+    }
+
+    #[test]
+    fn test_flatten() {
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Customer {
+            name: String,
+            #[yfb(flatten)]
+            address: Address,
+        }
+
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Address {
+            street: String,
+            #[validate(length(min = 2, message = "Too short"))]
+            city: String,
+        }
+
+        let binding = use_binding(Customer::default);
+        assert_eq!(binding.street_binding().name(), "customer.street");
+        assert_eq!(binding.city_binding().name(), "customer.city");
+
+        binding.street_binding().modifier().set("Main street");
+        assert_eq!(binding.model().address.street, "Main street");
+        assert!(binding.state().address.street.dirty());
+        assert!(binding.state().dirty());
+
+        binding.modifier().city().set("A");
+        assert_eq!(binding.model().address.city, "A");
+
+        let address = use_binding(Address::default);
+        assert_eq!(address.street_binding().name(), "address.street");
+        address.modifier().city().set("A");
+        assert_eq!(address.state().city.message().as_deref(), Some("Too short"));
+    }
+
+    #[test]
+    fn test_nested_flatten() {
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Customer {
+            name: String,
+            #[yfb(flatten)]
+            #[validate]
+            address: Address,
+        }
+
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Address {
+            street: String,
+            #[yfb(flatten)]
+            #[validate]
+            geo: Geo,
+        }
+
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Geo {
+            #[validate(range(min = -90.0, max = 90.0, message = "Out of range"))]
+            lat: f64,
+        }
+
+        let binding = use_binding(Customer::default);
+        assert_eq!(binding.lat_binding().name(), "customer.lat");
+        assert_eq!(binding.street_binding().name(), "customer.street");
+        assert_eq!(binding.name_binding().name(), "customer.name");
+
+        binding.lat_binding().modifier().set("91");
+        binding.modifier();
+        assert_eq!(binding.model().address.geo.lat, 91.0);
+        assert!(binding.validate().is_err());
+        assert_eq!(
+            binding.lat_binding().message().as_deref(),
+            Some("Out of range")
+        );
+        assert_eq!(
+            binding.messages_by_path(),
+            [("customer.lat".to_owned(), AttrValue::from("Out of range"))]
+        );
+
+        binding.modifier().lat().set("45");
+        assert_eq!(binding.state().address.geo.lat.message(), None);
+        assert!(binding.validate().is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Signup {
+            #[validate(length(min = 1, message = "Required"))]
+            name: String,
+            #[validate(range(min = 18, message = "Too young"))]
+            age: u32,
+        }
+
+        let binding = use_binding(Signup::default);
+        binding.modifier().age().set("20");
+        assert_eq!(binding.state().name.message(), None);
+
+        let errors = binding.submit().unwrap_err();
+        assert!(errors.field_errors().contains_key("name"));
+        assert_eq!(binding.state().name.message().as_deref(), Some("Required"));
+        assert_eq!(binding.state().age.message(), None);
+
+        // Shown messages are kept when other fields are modified
+        binding.modifier().age().set("21");
+        assert_eq!(binding.state().name.message().as_deref(), Some("Required"));
+
+        binding.modifier().name().set("John");
+        assert_eq!(binding.state().name.message(), None);
+        assert_eq!(
+            binding.submit().unwrap(),
+            Signup {
+                name: "John".into(),
+                age: 21
+            }
+        );
+    }
+
+    #[test]
+    fn test_touched() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Contact {
+            #[validate(length(min = 1, message = "Required"))]
+            name: String,
+            email: String,
+        }
+
+        let binding = use_binding(Contact::default);
+        assert!(!binding.state().touched());
+
+        binding.modifier().name().focus();
+        assert!(binding.state().focused());
+        assert!(binding.state().name.focused());
+        assert!(!binding.state().touched());
+
+        binding.modifier().name().blur();
+        assert!(!binding.state().focused());
+        assert!(binding.state().name.touched());
+        assert!(!binding.state().email.touched());
+        assert!(binding.state().touched());
+
+        // Validating the whole model touches all fields
+        assert!(binding.validate().is_err());
+        assert!(binding.state().email.touched());
+    }
+
+    #[test]
+    fn test_async_validator() {
+        async fn available(name: String) -> Result<(), validator::ValidationError> {
+            tokio::task::yield_now().await;
+            match name.as_str() {
+                "free" => Ok(()),
+                _ => Err(validator::ValidationError::new("taken")),
+            }
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Account {
+            #[validate(length(min = 3, message = "Too short"))]
+            #[yfb(async_validator = "available")]
+            name: String,
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, async {
+            let binding = use_binding(Account::default);
+
+            // Values failing the synchronous validation are not checked
+            binding.modifier().name().set("ab");
+            assert!(!binding.state().validating());
+
+            binding.modifier().name().set("taken");
+            assert!(binding.state().name.validating());
+            assert!(binding.state().validating());
+
+            let errors = binding.submit().unwrap_err();
+            assert_eq!(
+                errors.field_errors()["name"][0].code(),
+                crate::field::VALIDATING_CODE
+            );
+
+            binding.wait_validation().await;
+            assert!(!binding.state().validating());
+            assert_eq!(binding.state().name.message().as_deref(), Some("taken"));
+            assert!(binding.submit().is_err());
+
+            // The result for a stale value is dropped
+            binding.modifier().name().set("other");
+            binding.modifier().name().set("free");
+            binding.wait_validation().await;
+            assert_eq!(binding.state().name.message(), None);
+
+            binding.modifier().name().set("taken");
+            binding.modifier().name().set("free");
+            assert_eq!(binding.submit_async().await.unwrap().name, "free");
+        });
+    }
+
+    #[test]
+    fn test_debounce() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Search {
+            #[validate(length(min = 3, message = "Too short"))]
+            #[yfb(debounce_ms = 10)]
+            query: String,
+            #[validate(length(min = 3, message = "Too short"))]
+            name: String,
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, async {
+            let binding = use_binding(Search::default);
+
+            binding.modifier().query().set("a");
+            binding.modifier().query().set("ab");
+            binding.modifier().name().set("ab");
+            assert_eq!(binding.model().query, "ab");
+            assert_eq!(binding.state().query.message(), None);
+            assert_eq!(binding.state().name.message().as_deref(), Some("Too short"));
+
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            assert_eq!(
+                binding.state().query.message().as_deref(),
+                Some("Too short")
+            );
+
+            // Validating all fields does not wait for the debounce
+            binding.modifier().query().set("abc");
+            binding.modifier().query().set("ab");
+            assert!(binding.validate().is_err());
+            assert_eq!(
+                binding.state().query.message().as_deref(),
+                Some("Too short")
+            );
+        });
+    }
+
+    #[test]
+    fn test_schema_messages() {
+        fn validate_period(period: &Period) -> Result<(), validator::ValidationError> {
+            if period.end < period.start {
+                let mut error =
+                    validator::ValidationError::new("end_before_start").target_fields(&["end"]);
+                error.message = Some("End before start".into());
+                return Err(error);
+            }
+            if period.end - period.start > 10 {
+                let mut error = validator::ValidationError::new("too_long");
+                error.message = Some("Too long".into());
+                return Err(error);
+            }
+            Ok(())
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[validate(schema(function = "validate_period"))]
+        #[yfb(path = "crate")]
+        struct Period {
+            start: u32,
+            end: u32,
+        }
+
+        let binding = use_binding(Period::default);
+        binding.modifier().end().set("20");
+        assert_eq!(binding.messages(), ["Too long"]);
+        assert_eq!(binding.state().end.message(), None);
+
+        binding.modifier().start().set("25");
+        assert!(binding.messages().is_empty());
+        assert_eq!(
+            binding.state().end.message().as_deref(),
+            Some("End before start")
+        );
+        assert_eq!(binding.state().start.message(), None);
+
+        binding.modifier().end().set("26");
+        assert!(binding.messages().is_empty());
+        assert_eq!(binding.state().end.message(), None);
+        assert!(binding.validate().is_ok());
+    }
+
+    #[test]
+    fn test_messages_by_path() {
+        fn validate_event(event: &Event) -> Result<(), validator::ValidationError> {
+            match event.guests.len() > event.seats as usize {
+                true => Err(validator::ValidationError::new("Not enough seats")),
+                false => Ok(()),
+            }
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[validate(schema(function = "validate_event"))]
+        #[yfb(path = "crate")]
+        struct Event {
+            #[validate(length(min = 3))]
+            title: String,
+            seats: u32,
+            #[validate]
+            guests: Vec<Guest>,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Guest {
+            #[validate(email)]
+            email: String,
+        }
+
+        let binding = use_binding(Event::default);
+        assert_eq!(binding.messages_by_path(), []);
+
+        binding.modifier().guests().push(Guest {
+            email: "jane@example.com".into(),
+        });
+        binding.modifier().guests().push(Guest::default());
+        binding.modifier().seats().set("many");
+        let _ = binding.validate();
+
+        let by_path = |entries: Vec<(String, AttrValue)>| {
+            entries
+                .into_iter()
+                .map(|(path, message)| format!("{path}: {message}"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            by_path(binding.messages_by_path()),
+            [
+                "event: Not enough seats",
+                "event.title: Must be at least 3 characters long",
+                "event.guests[1].email: Must be a valid email address",
+            ]
+        );
+        assert_eq!(
+            by_path(binding.errors_by_path()),
+            ["event.seats: Invalid value 'many'"]
+        );
+
+        // Any visitor can walk the fields, e.g. a closure
+        let mut paths = Vec::new();
+        binding.visit(&mut |path: &str, _: &Field| paths.push(path.to_owned()));
+        assert_eq!(
+            paths,
+            [
+                "event.title",
+                "event.seats",
+                "event.guests[0].email",
+                "event.guests[1].email"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_errors() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Item {
+            #[validate(length(min = 1, message = "Required"))]
+            name: String,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            #[validate]
+            primary: Item,
+            #[validate]
+            items: Vec<Item>,
+        }
+
+        let binding = use_binding(|| Order {
+            primary: Item::default(),
+            items: vec![Item { name: "a".into() }, Item::default()],
+        });
+
+        assert!(binding.validate().is_err());
+        assert_eq!(
+            binding.state().primary.name.message().as_deref(),
+            Some("Required")
+        );
+        assert_eq!(binding.state().items.current[0].name.message(), None);
+        assert_eq!(
+            binding.state().items.current[1].name.message().as_deref(),
+            Some("Required")
+        );
+
+        binding.modifier().items().item_modifier(1).name().set("b");
+        binding.modifier().primary().name().set("c");
+        assert_eq!(binding.state().primary.name.message(), None);
+        assert_eq!(binding.state().items.current[1].name.message(), None);
+        assert!(binding.validate().is_ok());
+    }
+
+    #[test]
+    fn test_field_errors() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Account {
+            #[validate(
+                length(min = 8),
+                contains(pattern = "!", message = "Needs an exclamation mark")
+            )]
+            password: String,
+        }
+
+        let binding = use_binding(Account::default);
+        binding.modifier().password().set("secret");
+
+        let state = binding.state();
+        let codes = state
+            .password
+            .errors()
+            .map(|error| error.code().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["length", "contains"]);
+        assert_eq!(
+            state.password.message().as_deref(),
+            Some("Must be at least 8 characters long")
+        );
+
+        let length = state.password.errors_for("length").next().unwrap();
+        assert_eq!(length.message(), None);
+        assert_eq!(length.param("min").map(AttrValue::as_str), Some("8"));
+        assert_eq!(length.param("value").map(AttrValue::as_str), Some("secret"));
+        assert!(state.password.has_error("contains"));
+        drop(state);
+
+        binding.modifier().password().set("secret!");
+        assert!(!binding.state().password.has_error("contains"));
+        assert!(binding.state().password.has_error("length"));
+    }
+
+    #[test]
+    fn test_formatter() {
+        use crate::format::{Catalog, EnglishFormatter};
+
+        fn validate_account(account: &Account) -> Result<(), validator::ValidationError> {
+            if account.name == "admin" {
+                return Err(validator::ValidationError::new("name_taken"));
+            }
+            Ok(())
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[validate(schema(function = "validate_account"))]
+        #[yfb(path = "crate")]
+        struct Account {
+            #[validate(length(min = 3, max = 8))]
+            name: String,
+            #[validate(range(min = 18, message = "too_young"))]
+            age: u32,
+        }
+
+        let catalog: Catalog = "
+            # Dutch
+            length = Moet tussen {min} en {max} tekens lang zijn
+            too_young = Minimaal {min} jaar
+            parse = Ongeldige waarde '{value}'
+            name_taken = Naam is bezet
+        "
+        .parse()
+        .unwrap();
+
+        let binding = use_binding(Account::default).with_formatter(catalog);
+        binding.modifier().name().set("ab");
+        binding.modifier().age().set("12");
+
+        assert_eq!(
+            binding.state().name.message().as_deref(),
+            Some("Must be between 3 and 8 characters long")
+        );
+        assert_eq!(
+            binding.name_binding().message().as_deref(),
+            Some("Moet tussen 3 en 8 tekens lang zijn")
+        );
+        assert_eq!(
+            binding.age_binding().message().as_deref(),
+            Some("Minimaal 18 jaar")
+        );
+
+        binding.modifier().age().set("abc");
+        assert_eq!(
+            binding.state().age.error().as_deref(),
+            Some("Invalid value 'abc'")
+        );
+        assert_eq!(
+            binding
+                .state()
+                .age
+                .format_error(&*binding.formatter().unwrap().clone())
+                .as_deref(),
+            Some("Ongeldige waarde 'abc'")
+        );
+
+        binding.modifier().age().set("20");
+        assert_eq!(binding.state().age.error(), None);
+
+        binding.modifier().name().set("admin");
+        assert_eq!(binding.messages(), ["Naam is bezet"]);
+        assert_eq!(
+            binding.messages_by_path(),
+            [("account".to_owned(), AttrValue::from("Naam is bezet"))]
+        );
+        assert_eq!(
+            binding.state().messages().format(&EnglishFormatter),
+            ["name_taken"]
+        );
+        assert_eq!(
+            "key without template".parse::<Catalog>().err(),
+            Some(crate::format::ParseCatalogError { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_validator() {
+        use crate::{
+            model::Validator,
+            validation::{FieldError, TargetFields, ValidationErrors, SCHEMA_KEY},
+        };
+
+        #[derive(Clone, Debug, Default, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        struct Search {
+            query: String,
+        }
+
+        let binding = use_binding(Search::default);
+        binding.modifier().query().set("anything");
+        assert_eq!(binding.state().query.message(), None);
+        assert_eq!(binding.submit().unwrap().query, "anything");
+
+        #[derive(Clone, Debug, Default, PartialEq, Model)]
+        #[yfb(path = "crate")]
+        struct Pin {
+            code: String,
+        }
+
+        impl Validator for Pin {
+            fn validate(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                if self.code.len() != 4 {
+                    errors.add("code", FieldError::new("pin"));
+                }
+                if self.code == "0000" {
+                    errors.add(SCHEMA_KEY, FieldError::new("weak").target_fields(&["code"]));
+                }
+                errors.into_result()
+            }
+        }
+
+        let binding = use_binding(Pin::default);
+        binding.modifier().code().set("12");
+        assert_eq!(binding.state().code.message().as_deref(), Some("pin"));
+
+        binding.modifier().code().set("0000");
+        assert_eq!(binding.state().code.message().as_deref(), Some("weak"));
+
+        binding.modifier().code().set("1234");
+        assert_eq!(binding.state().code.message(), None);
+        assert_eq!(binding.submit().unwrap().code, "1234");
+    }
+
+    #[test]
+    fn test_tuple_and_enum_validator() {
+        use crate::{
+            model::Validator,
+            validation::{FieldError, ValidationErrors, SCHEMA_KEY},
+        };
+
+        #[derive(Clone, Debug, PartialEq, Model)]
+        #[yfb(path = "crate")]
+        struct Meters(f64);
+
+        impl Validator for Meters {
+            fn validate(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                errors.add("0", FieldError::new("too_far"));
+                errors.add(SCHEMA_KEY, FieldError::new("unreachable"));
+                Err(errors)
+            }
+        }
+
+        let binding = use_binding(|| Meters(1.0));
+        binding.modifier()._0().set("2");
+        assert_eq!(binding.state().0.message().as_deref(), Some("too_far"));
+        assert!(binding.validate().is_err());
+        assert_eq!(binding.messages(), ["unreachable"]);
+
+        #[derive(Clone, Debug, PartialEq, Model)]
+        #[yfb(path = "crate")]
+        enum Shipping {
+            Pickup,
+            Courier { address: String },
+        }
+
+        impl Validator for Shipping {
+            fn validate(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                match self {
+                    Shipping::Pickup => errors.add(SCHEMA_KEY, FieldError::new("closed")),
+                    Shipping::Courier { .. } => errors.add("address", FieldError::new("unknown")),
+                }
+                Err(errors)
+            }
+        }
+
+        let binding = use_binding(|| Shipping::Pickup);
+        binding.modifier().courier_address().set("Main St");
+        assert_eq!(
+            binding.state().courier_address.message().as_deref(),
+            Some("unknown")
+        );
+        assert!(binding.submit().is_err());
+
+        binding.modifier().switch_variant(ShippingVariant::Pickup);
+        assert!(binding.submit().is_err());
+        assert_eq!(binding.messages(), ["closed"]);
+    }
+
+    #[cfg(feature = "garde")]
+    #[test]
+    fn test_garde() {
+        use crate::validation::{ValidationErrorsKind, GARDE_CODE};
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, garde::Validate)]
+        #[yfb(path = "crate", garde)]
+        struct Contact {
+            #[garde(length(min = 3))]
+            name: String,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, garde::Validate)]
+        #[yfb(path = "crate", garde)]
+        struct Meters(#[garde(range(max = 10.0))] f64);
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, garde::Validate)]
+        #[yfb(path = "crate", garde)]
+        struct Order {
+            #[garde(length(min = 3))]
+            name: String,
+            #[garde(dive)]
+            primary: Contact,
+            #[garde(dive)]
+            contacts: Vec<Contact>,
+            #[garde(dive)]
+            distance: Meters,
+        }
+
+        let binding = use_binding(|| Order {
+            contacts: vec![Contact { name: "abc".into() }, Contact::default()],
+            ..Default::default()
+        });
+        binding.modifier().name().set("ab");
+        assert_eq!(
+            binding.state().name.message().as_deref(),
+            Some("length is lower than 3")
+        );
+
+        let errors = binding.validate().unwrap_err();
+        let Some(ValidationErrorsKind::Field(name)) = errors.get("name") else {
+            panic!("expected errors of `name`");
+        };
+        assert_eq!(name[0].code(), GARDE_CODE);
+        assert!(matches!(
+            errors.get("primary"),
+            Some(ValidationErrorsKind::Struct(primary)) if primary.get("name").is_some()
+        ));
+        assert!(matches!(
+            errors.get("contacts"),
+            Some(ValidationErrorsKind::List(items)) if items.keys().eq([&1])
+        ));
+        assert!(binding.state().primary.name.message().is_some());
+        assert_eq!(binding.state().contacts.current[0].name.message(), None);
+        assert!(binding.state().contacts.current[1].name.message().is_some());
+        assert_eq!(binding.state().distance.0.message(), None);
+
+        binding.modifier().distance()._0().set("11");
+        assert_eq!(
+            binding.state().distance.0.message().as_deref(),
+            Some("greater than 10")
+        );
+
+        binding.modifier().name().set("abc");
+        binding.modifier().primary().name().set("abc");
+        binding
+            .modifier()
+            .contacts()
+            .item_modifier(1)
+            .name()
+            .set("abc");
+        binding.modifier().distance()._0().set("5");
+        assert!(binding.validate().is_ok());
+        assert_eq!(binding.state().contacts.current[1].name.message(), None);
+    }
+
+    #[test]
+    fn test_history() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Note {
+            #[validate(length(max = 7))]
+            title: String,
+            tags: Vec<String>,
+        }
+
+        let binding = use_binding_with_history(Note::default, Coalesce::Words);
+        assert!(!binding.can_undo());
+
+        for title in ["h", "he", "hello", "hello ", "hello w", "hello wo"] {
+            binding.modifier().title().set(title);
+        }
+        binding.modifier().tags().push("todo".to_owned());
+        assert!(binding.can_undo());
+        assert!(binding.state().title.message().is_some());
+
+        // Steps can be undone from any binding mapped from the root
+        assert!(binding.tags_binding().undo());
+        assert_eq!(binding.model().tags, Vec::<String>::new());
+        assert_eq!(binding.model().title, "hello wo");
+
+        assert!(binding.undo());
+        assert_eq!(binding.model().title, "hello ");
+        assert_eq!(binding.state().title.value(), "hello ");
+        assert_eq!(binding.state().title.message(), None);
+
+        assert!(binding.undo());
+        assert_eq!(binding.model().title, "");
+        assert!(!binding.can_undo());
+        assert!(!binding.undo());
+
+        assert!(binding.redo());
+        assert_eq!(binding.model().title, "hello ");
+        assert!(binding.can_redo());
+
+        // A new change discards the steps that could be redone
+        binding.modifier().title().set("hello!");
+        assert!(!binding.can_redo());
+        assert!(!binding.redo());
+
+        // Losing focus ends the step
+        binding.modifier().title().blur();
+        binding.modifier().title().set("hello!!");
+        assert!(binding.undo());
+        assert_eq!(binding.model().title, "hello!");
+
+        let binding = use_binding_with_history(Note::default, Coalesce::Never);
+        binding.modifier().title().set("a");
+        binding.modifier().title().set("ab");
+        assert!(binding.undo());
+        assert_eq!(binding.model().title, "a");
+
+        // Bindings without history ignore undo and redo
+        let binding = use_binding(Note::default);
+        binding.modifier().title().set("a");
+        assert!(!binding.can_undo());
+        assert!(!binding.undo());
+    }
+
+    #[test]
+    fn test_reset() {
+        #[derive(Clone, Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Profile {
+            #[validate(length(min = 2))]
+            name: String,
+            age: u32,
+            tags: Vec<String>,
+            nickname: Option<String>,
+            #[yfb(skip)]
+            loaded: bool,
+        }
+
+        let initial = Profile {
+            name: "Ann".into(),
+            age: 30,
+            tags: vec!["a".into(), "b".into()],
+            nickname: None,
+            loaded: true,
+        };
+        let binding = use_binding(|| initial.clone());
+
+        binding.modifier().name().set("X");
+        binding.modifier().age().set("abc");
+        binding.modifier().tags().remove(0);
+        binding.modifier().tags().remove(0);
+        binding.modifier().tags().push("c".into());
+        binding.modifier().nickname().replace("nick".into());
+        assert!(binding.state().name.message().is_some());
+        assert!(binding.state().age.error().is_some());
+        assert_eq!(binding.model().tags, vec!["c".to_owned()]);
+
+        assert_eq!(binding.initial_model(), initial);
+
+        binding.modifier().reset();
+        assert_eq!(*binding.model(), initial);
+        assert!(!binding.state().dirty());
+        assert_eq!(binding.state().name.message(), None);
+        assert_eq!(binding.state().age.error(), None);
+
+        // Fields and lists can be reset on their own
+        binding.modifier().name().set("Bob");
+        binding.modifier().tags().push("d".into());
+        binding.tags_binding().modifier().reset();
+        assert_eq!(binding.model().tags, initial.tags);
+        assert_eq!(binding.model().name, "Bob");
+        binding.name_binding().modifier().reset();
+        assert_eq!(binding.model().name, "Ann");
+
+        #[derive(Clone, Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Payment {
+            Cash,
+            Card { number: String },
+        }
+
+        let payment = use_named_binding("payment", || Payment::Card {
+            number: "1234".into(),
+        });
+        payment.card_number_binding().modifier().set("5678");
+        payment.switch_variant(PaymentVariant::Cash);
+        assert_eq!(
+            payment.initial_model(),
+            Payment::Card {
+                number: "1234".into()
+            }
+        );
+
+        payment.modifier().reset();
+        assert_eq!(
+            *payment.model(),
+            Payment::Card {
+                number: "1234".into()
+            }
+        );
+        assert!(!payment.state().dirty());
+    }
+
+    #[test]
+    fn test_reset_removed_items_without_initial_model() {
+        #[derive(Clone, Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            items: Vec<Item>,
+        }
+
+        #[derive(Clone, Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Item {
+            name: String,
+            // Items with skipped fields can't be rebuilt from their state
+            #[yfb(skip)]
+            id: u32,
+        }
+
+        let item = |name: &str, id| Item {
+            name: name.into(),
+            id,
+        };
+        let binding = use_binding(|| Order {
+            items: vec![item("a", 1), item("b", 2), item("c", 3)],
+        });
+        binding.modifier().items().remove(1);
+        binding
+            .items_binding()
+            .map_item(0)
+            .modifier()
+            .name()
+            .set("x");
+
+        // Restoring part of the list would drop the removed item and the ones after it
+        let current = vec![item("x", 1), item("c", 3)];
+        assert_eq!(binding.initial_model().items, current);
+        binding.modifier().reset();
+        assert_eq!(binding.model().items, current);
+    }
+
+    #[test]
+    fn test_changes() {
+        #[derive(Debug, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            customer: String,
+            tags: Vec<String>,
+            delivery: Delivery,
+        }
+
+        #[derive(Debug, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Delivery {
+            Pickup,
+            Shipping { address: String },
+        }
+
+        let binding = use_binding(|| Order {
+            customer: "Jane".into(),
+            tags: vec!["a".into(), "b".into()],
+            delivery: Delivery::Pickup,
+        });
+        assert_eq!(binding.changes(), []);
+
+        let change =
+            |path: &str, old: Option<&'static str>, new: Option<&'static str>| FieldChange {
+                path: path.into(),
+                old: old.map(AttrValue::from),
+                new: new.map(AttrValue::from),
+            };
+
+        binding.modifier().customer().set("John");
+        binding.modifier().tags().remove(0);
+        binding
+            .delivery_binding()
+            .modifier()
+            .shipping_address()
+            .set("Main street");
+        assert_eq!(
+            binding.changes(),
+            [
+                change("order.customer", Some("Jane"), Some("John")),
+                change("order.tags[0]", Some("a"), Some("b")),
+                change("order.tags[1]", Some("b"), None),
+                change("order.delivery", Some("pickup"), Some("shipping")),
+                change("order.delivery.shipping.address", None, Some("Main street")),
+            ]
+        );
+
+        // Added items are reported with all their fields, even when empty
+        binding.modifier().tags().push("b".into());
+        binding.modifier().tags().push("".into());
+        binding.modifier().customer().set("Jane");
+        assert_eq!(
+            binding.changes()[..2],
+            [
+                change("order.tags[0]", Some("a"), Some("b")),
+                change("order.tags[2]", None, Some("")),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_patches() {
+        use serde_json::json;
+
+        #[derive(Clone, Debug, PartialEq, Model, Validate, serde::Serialize)]
+        #[yfb(path = "crate")]
+        struct Profile {
+            name: String,
+            email: Option<String>,
+            tags: Vec<String>,
+        }
+
+        let binding = use_binding(|| Profile {
+            name: "Jane".into(),
+            email: Some("jane@example.com".into()),
+            tags: vec!["a".into(), "b/c".into()],
+        });
+        assert_eq!(binding.merge_patch().unwrap(), json!({}));
+        assert_eq!(binding.json_patch().unwrap(), json!([]));
+
+        binding.modifier().name().set("John");
+        binding.modifier().email().take();
+        binding.modifier().tags().remove(0);
+        assert_eq!(
+            binding.merge_patch().unwrap(),
+            json!({ "name": "John", "email": null, "tags": ["b/c"] })
+        );
+        assert_eq!(
+            binding.json_patch().unwrap(),
+            json!([
+                { "op": "replace", "path": "/email", "value": null },
+                { "op": "replace", "path": "/name", "value": "John" },
+                { "op": "replace", "path": "/tags/0", "value": "b/c" },
+                { "op": "remove", "path": "/tags/1" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        use crate::{
+            encoding::{from_urlencoded, to_pairs, to_urlencoded},
+            field::PARSE_CODE,
+        };
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            customer: String,
+            lines: Vec<Line>,
+            note: Option<String>,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate", default)]
+        struct Line {
+            product: String,
+            quantity: u32,
+        }
+
+        let order = Order {
+            customer: "Jane & John".into(),
+            lines: vec![
+                Line {
+                    product: "apple".into(),
+                    quantity: 2,
+                },
+                Line {
+                    product: "pear".into(),
+                    quantity: 1,
+                },
+            ],
+            note: None,
+        };
+        assert_eq!(
+            to_pairs(&order, "order")
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("order.customer", "Jane & John"),
+                ("order.lines[0].product", "apple"),
+                ("order.lines[0].quantity", "2"),
+                ("order.lines[1].product", "pear"),
+                ("order.lines[1].quantity", "1"),
+            ]
+        );
+
+        let encoded = to_urlencoded(&order, "order");
+        let decoded = from_urlencoded(Order::default(), "order", &encoded);
+        assert!(decoded.is_valid());
+        assert_eq!(decoded.model, order);
+
+        // The state takes the decoded values as its initial values
+        let binding = use_binding(Order::default);
+        binding.restore(decoded.model, decoded.state);
+        assert!(!binding.state().lines.dirty());
+        binding.modifier().lines().remove(1);
+        assert!(binding.state().lines.dirty());
+
+        let decoded = from_urlencoded(
+            order.clone(),
+            "order",
+            "order.customer=Jane&order.lines%5B0%5D.quantity=many&order.lines[5].product=kiwi\
+             &order.note=fragile&other.customer=John",
+        );
+        assert_eq!(decoded.model.customer, "Jane");
+        assert_eq!(decoded.model.lines, order.lines);
+        assert_eq!(decoded.model.note.as_deref(), Some("fragile"));
+        assert_eq!(decoded.errors.len(), 1);
+        assert_eq!(decoded.errors[0].0, "order.lines[0].quantity");
+        assert_eq!(decoded.errors[0].1.code(), PARSE_CODE);
+        assert_eq!(
+            decoded.errors[0].1.param("value").map(AttrValue::as_str),
+            Some("many")
+        );
+    }
+
+    #[test]
+    fn test_query_binding() {
+        use crate::query::{save_query, MemoryQuery, UrlQuery};
+
+        #[derive(Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Filter {
+            status: String,
+            tags: Vec<String>,
+            page: Option<u32>,
+            sort: Sort,
+        }
+
+        #[derive(Debug, Default, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Sort {
+            #[default]
+            Newest,
+            Price {
+                descending: bool,
+            },
+        }
+
+        let url = MemoryQuery::new(
+            "lang=en&filter.status=open&filter.tags%5B0%5D=a&filter.tags[1]=b&filter.tags[3]=d\
+             &filter.page=x&filter.sort.price.descending=true&filter.unknown=1",
+        );
+        let binding = use_query_binding_with_url(url.clone(), Filter::default);
+        assert_eq!(
+            *binding.model(),
+            Filter {
+                status: "open".into(),
+                tags: vec!["a".into(), "b".into()],
+                page: None,
+                sort: Sort::Price { descending: true },
+            }
+        );
+        // The loaded values are the initial values of the fields
+        assert!(!binding.state().status.dirty());
+        assert!(!binding.state().tags.dirty());
+
+        binding.modifier().status().set("closed");
+        binding.modifier().tags().remove(0);
+        binding.modifier().page().replace(2);
+        save_query(&url, &binding);
+        assert_eq!(
+            url.get(),
+            "lang=en&filter.status=closed&filter.tags%5B0%5D=b&filter.page=2\
+             &filter.sort=price&filter.sort.price.descending=true"
+        );
+
+        // Variants are restored by their name
+        binding
+            .sort_binding()
+            .modifier()
+            .replace_model(Sort::Newest);
+        save_query(&url, &binding);
+        let binding = use_query_binding_with_url(url.clone(), || Filter {
+            sort: Sort::Price { descending: false },
+            ..Default::default()
+        });
+        assert_eq!(binding.model().sort, Sort::Newest);
+        assert_eq!(binding.model().page, Some(2));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_state() {
+        #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+        #[yfb(path = "crate")]
+        struct Order {
+            #[validate(length(min = 3))]
+            customer: String,
+            quantity: u32,
+            lines: Vec<Wrapped<u32>>,
+            delivery: Delivery,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq, Model)]
+        #[yfb(path = "crate", no_validate)]
+        enum Delivery {
+            #[default]
+            Pickup,
+            Shipping {
+                address: String,
+            },
+        }
+
+        let binding = use_binding(|| Order {
+            quantity: 1,
+            ..Default::default()
+        });
+        binding.modifier().customer().set("ab");
+        binding.modifier().quantity().set("many");
+        binding.modifier().lines().push(Wrapped(5));
+        binding
+            .delivery_binding()
+            .shipping_address_binding()
+            .modifier()
+            .set("Main street");
+        binding.modifier().customer().blur();
+
+        let state = serde_json::to_string(&*binding.state()).unwrap();
+        let model = binding.model().clone();
+
+        let restored = use_binding(Order::default);
+        let valid = restored.clone();
+        restored.restore(model.clone(), serde_json::from_str(&state).unwrap());
+        assert_ne!(restored, valid);
+
+        assert_eq!(*restored.model(), model);
+        assert!(restored.state().dirty());
+        assert!(restored.state().customer.touched());
+        assert_eq!(
+            restored.state().customer.message(),
+            binding.state().customer.message()
+        );
+        assert_eq!(
+            restored.state().quantity.error().as_deref(),
+            Some("Invalid value 'many'")
+        );
+        assert_eq!(
+            restored.state().delivery.variant(),
+            DeliveryVariant::Shipping
+        );
+
+        // The baseline is restored along with the values
+        restored.modifier().customer().set("");
+        assert!(!restored.state().customer.dirty());
+
+        // The restored state rerenders the binding again
+        let valid = restored.clone();
+        restored.modifier().quantity().set("2");
+        assert_ne!(restored, valid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_persisted_binding() {
+        use crate::persist::{save_draft, MemoryStorage};
+
+        #[derive(
+            Clone, Debug, Default, PartialEq, Model, Validate, serde::Serialize, serde::Deserialize,
+        )]
+        #[yfb(path = "crate")]
+        struct Application {
+            #[validate(length(min = 3))]
+            name: String,
+            motivation: String,
+        }
+
+        let storage = MemoryStorage::default();
+        let binding = use_persisted_binding_with_storage(
+            "application",
+            storage.clone(),
+            Application::default,
+        );
+        assert_eq!(storage.get("application"), None);
+
+        binding.modifier().name().set("Al");
+        binding.modifier().motivation().set("Loves forms");
+        save_draft(&storage, "application", &binding);
+
+        // The draft is loaded instead of the initial model after a reload
+        let reloaded = use_persisted_binding_with_storage(
+            "application",
+            storage.clone(),
+            Application::default,
+        );
+        assert_eq!(*reloaded.model(), *binding.model());
+        assert!(reloaded.state().dirty());
+        assert_eq!(
+            reloaded.state().name.message(),
+            binding.state().name.message()
+        );
+
+        // Changes of the loaded state rerender the binding
+        let valid = reloaded.clone();
+        reloaded.modifier().name().set("Alice");
+        assert_ne!(reloaded, valid);
+        assert_eq!(reloaded.state().name.message(), None);
+
+        // Drafts that don't match the model anymore are ignored
+        storage.set("application", r#"{"name":"Al"}"#);
+        let binding = use_persisted_binding_with_storage(
+            "application",
+            storage.clone(),
+            Application::default,
+        );
+        assert_eq!(*binding.model(), Application::default());
+
+        storage.remove("application");
+        assert_eq!(storage.get("application"), None);
+    }
 }
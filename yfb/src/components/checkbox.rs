@@ -10,6 +10,10 @@ pub struct CheckboxProps {
     pub classes: Classes,
     #[prop_or_default]
     pub ontoggle: Callback<bool>,
+    #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
 }
 
 #[function_component(Checkbox)]
@@ -18,6 +22,8 @@ pub fn checkbox(
         binding,
         classes,
         ontoggle,
+        onfocus,
+        onblur,
     }: &CheckboxProps,
 ) -> Html {
     let ontoggle = {
@@ -31,6 +37,24 @@ pub fn checkbox(
         })
     };
 
+    let onfocus = {
+        let binding = binding.clone();
+
+        onfocus.reform(move |e: FocusEvent| {
+            binding.modifier().focus();
+            e
+        })
+    };
+
+    let onblur = {
+        let binding = binding.clone();
+
+        onblur.reform(move |e: FocusEvent| {
+            binding.modifier().blur();
+            e
+        })
+    };
+
     html! {
         <input
             id={binding.name()}
@@ -38,6 +62,8 @@ pub fn checkbox(
             class={classes.clone()}
             type="checkbox"
             oninput={ontoggle}
+            {onfocus}
+            {onblur}
             checked={*binding.model()}
             class={classes.clone()}
          />
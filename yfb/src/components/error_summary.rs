@@ -0,0 +1,79 @@
+use yew::prelude::*;
+
+use crate::{
+    field::Field,
+    format::MessageFormatter,
+    model::{Messages, Visitor},
+    prelude::*,
+};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ErrorSummaryProps<T: Model> {
+    pub binding: Binding<T>,
+    /// Heading shown above the list of errors
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
+    #[prop_or_default]
+    pub classes: Classes,
+}
+
+/// Lists the messages and parse errors of all fields of a model, linking to the inputs of the fields by their binding names.
+/// Messages of the model as a whole are listed without a link. Nothing is rendered while the model has no errors.
+#[function_component(ErrorSummary)]
+pub fn error_summary<T: Model>(
+    ErrorSummaryProps {
+        binding,
+        title,
+        classes,
+    }: &ErrorSummaryProps<T>,
+) -> Html {
+    let formatter = use_formatter();
+    let formatter = binding.formatter().cloned().unwrap_or(formatter);
+
+    let mut summary = Summary {
+        formatter: &*formatter,
+        entries: Vec::new(),
+    };
+    binding.visit(&mut summary);
+    if summary.entries.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class={classes.clone()} role="alert">
+            if let Some(title) = title {
+                <h2>{title}</h2>
+            }
+            <ul>
+                { for summary.entries.into_iter().map(|(target, message)| match target {
+                    Some(target) => html! { <li><a href={format!("#{target}")}>{message}</a></li> },
+                    None => html! { <li>{message}</li> },
+                })}
+            </ul>
+        </div>
+    }
+}
+
+/// Collects the errors of a model, with the id of the input to link to for errors of fields
+struct Summary<'a> {
+    formatter: &'a dyn MessageFormatter,
+    entries: Vec<(Option<String>, AttrValue)>,
+}
+
+impl Visitor for Summary<'_> {
+    fn field(&mut self, path: &str, field: &Field) {
+        // The message refers to the last value that could be parsed, the parse error to what is shown
+        let message = field
+            .format_error(self.formatter)
+            .or_else(|| field.format_message(self.formatter));
+        if let Some(message) = message {
+            self.entries.push((Some(path.to_owned()), message));
+        }
+    }
+
+    fn messages(&mut self, _path: &str, messages: &Messages) {
+        let messages = messages.format(self.formatter);
+        self.entries
+            .extend(messages.into_iter().map(|message| (None, message)));
+    }
+}
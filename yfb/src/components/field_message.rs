@@ -0,0 +1,64 @@
+use yew::prelude::*;
+
+use super::message_id;
+use crate::prelude::*;
+
+/// Selects which message of a field a [`FieldMessage`] shows
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MessageKind {
+    /// The parse error of the field, or else its validation message
+    #[default]
+    Any,
+    /// Only the error for input that could not be parsed
+    Error,
+    /// Only the message of the failed validation
+    Message,
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct FieldMessageProps<T: Value> {
+    pub binding: Binding<T>,
+    #[prop_or_default]
+    pub kind: MessageKind,
+    #[prop_or_default]
+    pub classes: Classes,
+    /// Classes that are applied when a parse error is shown
+    #[prop_or_default]
+    pub classes_error: Classes,
+}
+
+/// Shows the message of a field, nothing is rendered while the field has none.
+/// The element gets an id derived from the binding's name, an [`Input`](super::Input) with `described_by_message` refers to it
+/// through `aria-describedby`.
+#[function_component(FieldMessage)]
+pub fn field_message<T: Value>(
+    FieldMessageProps {
+        binding,
+        kind,
+        classes,
+        classes_error,
+    }: &FieldMessageProps<T>,
+) -> Html {
+    let formatter = use_formatter();
+    let formatter = binding.formatter().cloned().unwrap_or(formatter);
+
+    let state = binding.state();
+    let error = match kind {
+        MessageKind::Any | MessageKind::Error => state.format_error(&*formatter),
+        MessageKind::Message => None,
+    };
+    let (text, classes) = match (error, kind) {
+        (Some(error), _) => (error, classes!(classes.clone(), classes_error.clone())),
+        (None, MessageKind::Error) => return html! {},
+        (None, _) => match state.format_message(&*formatter) {
+            Some(message) => (message, classes.clone()),
+            None => return html! {},
+        },
+    };
+
+    html! {
+        <div id={message_id(binding.name())} class={classes}>
+            {text}
+        </div>
+    }
+}
@@ -4,6 +4,7 @@ use yew::{
     prelude::*,
 };
 
+use super::{message_id, validity_classes};
 use crate::prelude::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -60,12 +61,23 @@ pub struct InputProps<T: Value> {
     /// Classes that are applied when the field is dirty and valid
     #[prop_or_default]
     pub classes_valid: Classes,
+    /// Apply `classes_invalid` once the field has been touched instead of once it is dirty
+    #[prop_or_default]
+    pub invalid_after_touched: bool,
     #[prop_or_default]
     pub oninput: Callback<InputEvent>,
     #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    #[prop_or_default]
     pub tabindex: Option<AttrValue>,
     #[prop_or_default]
     pub hidden: bool,
+    /// Refers to the [`FieldMessage`](super::FieldMessage) of the field through `aria-describedby` while it has something to show.
+    /// Only set this when a `FieldMessage` of [`MessageKind::Any`](super::MessageKind::Any) is rendered for the same binding.
+    #[prop_or_default]
+    pub described_by_message: bool,
 }
 
 #[function_component(Input)]
@@ -80,20 +92,23 @@ pub fn input<T: Value>(
         classes,
         classes_invalid,
         classes_valid,
+        invalid_after_touched,
         oninput,
+        onfocus,
+        onblur,
         tabindex,
         hidden,
+        described_by_message,
     }: &InputProps<T>,
 ) -> Html {
     let classes = classes!(
         classes.clone(),
-        binding
-            .state()
-            .dirty()
-            .then(|| match binding.state().valid() {
-                true => classes_valid.clone(),
-                false => classes_invalid.clone(),
-            })
+        validity_classes(
+            &binding.state(),
+            classes_valid,
+            classes_invalid,
+            *invalid_after_touched
+        )
     );
 
     let oninput = {
@@ -107,6 +122,31 @@ pub fn input<T: Value>(
         })
     };
 
+    let onfocus = {
+        let binding = binding.clone();
+
+        onfocus.reform(move |e: FocusEvent| {
+            binding.modifier().focus();
+            e
+        })
+    };
+
+    let onblur = {
+        let binding = binding.clone();
+
+        onblur.reform(move |e: FocusEvent| {
+            binding.modifier().blur();
+            e
+        })
+    };
+
+    // The `FieldMessage` only renders its element while the field has something to show
+    let describedby = {
+        let state = binding.state();
+        let shown = !state.valid() || state.parse_error().is_some();
+        (*described_by_message && shown).then(|| message_id(binding.name()))
+    };
+
     let autocomplete = if *autocomplete { "on" } else { "off" };
 
     html! {
@@ -118,8 +158,11 @@ pub fn input<T: Value>(
             type={*input_type}
             {autocomplete}
             {placeholder}
+            aria-describedby={describedby}
             value={binding.state().value()}
             {oninput}
+            {onfocus}
+            {onblur}
             disabled={*disabled}
             {tabindex}
             hidden={*hidden}
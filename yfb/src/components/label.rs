@@ -0,0 +1,28 @@
+use yew::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct LabelProps<T: Value> {
+    pub binding: Binding<T>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Labels the input of a binding, which the components of this module render with the binding's name as id
+#[function_component(Label)]
+pub fn label<T: Value>(
+    LabelProps {
+        binding,
+        classes,
+        children,
+    }: &LabelProps<T>,
+) -> Html {
+    html! {
+        <label for={binding.name()} class={classes.clone()}>
+            {children.clone()}
+        </label>
+    }
+}
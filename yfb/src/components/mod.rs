@@ -1,13 +1,52 @@
 mod checkbox;
+mod error_summary;
+mod field_message;
 mod input;
+mod label;
+mod radio;
 mod select;
 mod textarea;
 
+use yew::{AttrValue, Classes};
+
+use crate::{
+    field::Field,
+    model::{Dirty, Touched},
+};
+
 #[doc(inline)]
 pub use checkbox::{Checkbox, CheckboxProps};
 #[doc(inline)]
+pub use error_summary::{ErrorSummary, ErrorSummaryProps};
+#[doc(inline)]
+pub use field_message::{FieldMessage, FieldMessageProps, MessageKind};
+#[doc(inline)]
 pub use input::{Input, InputProps, InputType};
 #[doc(inline)]
+pub use label::{Label, LabelProps};
+#[doc(inline)]
+pub use radio::{Radio, RadioGroup, RadioGroupProps, RadioProps};
+#[doc(inline)]
 pub use select::{Select, SelectOption, SelectOptionProps, SelectProps};
 #[doc(inline)]
 pub use textarea::{TextArea, TextAreaProps};
+
+/// Selects the classes reflecting the validity of `field`.
+/// They apply once the field is dirty, or for `classes_invalid` once touched when `invalid_after_touched` is set.
+pub(crate) fn validity_classes(
+    field: &Field,
+    classes_valid: &Classes,
+    classes_invalid: &Classes,
+    invalid_after_touched: bool,
+) -> Option<Classes> {
+    match field.valid() {
+        true => field.dirty().then(|| classes_valid.clone()),
+        false if invalid_after_touched => field.touched().then(|| classes_invalid.clone()),
+        false => field.dirty().then(|| classes_invalid.clone()),
+    }
+}
+
+/// Returns the id of the [`FieldMessage`] of the input called `name`
+pub(crate) fn message_id(name: &str) -> AttrValue {
+    format!("{name}-message").into()
+}
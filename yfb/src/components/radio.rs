@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::{html::ChildrenRenderer, prelude::*, virtual_dom::VChild};
+
+use super::validity_classes;
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct RadioGroupProps<T: Value> {
+    pub binding: Binding<T>,
+    pub children: ChildrenRenderer<VChild<Radio>>,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// Classes of the element wrapping the radio buttons
+    #[prop_or_default]
+    pub group_classes: Classes,
+    /// Classes of the radio buttons
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub classes_valid: Classes,
+    #[prop_or_default]
+    pub classes_invalid: Classes,
+    /// Apply `classes_invalid` once the field has been touched instead of once it is dirty
+    #[prop_or_default]
+    pub invalid_after_touched: bool,
+    #[prop_or_default]
+    pub onchange: Callback<Event>,
+    #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+}
+
+/// Radio buttons sharing the binding's name, the one whose value matches the field is checked.
+/// The group gets the binding's name as id, its radio buttons the name followed by their index.
+#[function_component(RadioGroup)]
+pub fn radio_group<T: Value>(
+    RadioGroupProps {
+        binding,
+        children,
+        disabled,
+        group_classes,
+        classes,
+        classes_valid,
+        classes_invalid,
+        invalid_after_touched,
+        onchange,
+        onfocus,
+        onblur,
+    }: &RadioGroupProps<T>,
+) -> Html {
+    let checked = binding.state().value().clone();
+    let classes = classes!(
+        classes.clone(),
+        validity_classes(
+            &binding.state(),
+            classes_valid,
+            classes_invalid,
+            *invalid_after_touched
+        )
+    );
+
+    let onchange = {
+        let binding = binding.clone();
+
+        onchange.reform(move |e: Event| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                if input.checked() {
+                    binding.modifier().set(input.value());
+                }
+            }
+
+            e
+        })
+    };
+
+    let onfocus = {
+        let binding = binding.clone();
+
+        onfocus.reform(move |e: FocusEvent| {
+            binding.modifier().focus();
+            e
+        })
+    };
+
+    let onblur = {
+        let binding = binding.clone();
+
+        onblur.reform(move |e: FocusEvent| {
+            binding.modifier().blur();
+            e
+        })
+    };
+
+    let name = binding.name();
+
+    html! {
+        <div id={name} role="radiogroup" class={group_classes.clone()}>
+            { for children.iter().enumerate().map(move |(index, mut radio)| {
+                let props = Rc::make_mut(&mut radio.props);
+                props.id = format!("{name}-{index}").into();
+                props.name = name.clone();
+                props.checked = props.value == checked;
+                props.group_disabled = *disabled;
+                props.classes = classes.clone();
+                props.onchange = onchange.clone();
+                props.onfocus = onfocus.clone();
+                props.onblur = onblur.clone();
+                radio
+            })}
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct RadioProps {
+    pub value: AttrValue,
+    /// Content of the radio button's label, the value when missing
+    #[prop_or_default]
+    pub children: Option<Children>,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    id: AttrValue,
+    #[prop_or_default]
+    name: AttrValue,
+    #[prop_or_default]
+    checked: bool,
+    #[prop_or_default]
+    group_disabled: bool,
+    #[prop_or_default]
+    classes: Classes,
+    #[prop_or_default]
+    onchange: Callback<Event>,
+    #[prop_or_default]
+    onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    onblur: Callback<FocusEvent>,
+}
+
+#[function_component(Radio)]
+pub fn radio(
+    RadioProps {
+        value,
+        children,
+        disabled,
+        id,
+        name,
+        checked,
+        group_disabled,
+        classes,
+        onchange,
+        onfocus,
+        onblur,
+    }: &RadioProps,
+) -> Html {
+    html! {
+        <>
+            <input
+                type="radio"
+                {id}
+                {name}
+                {value}
+                checked={*checked}
+                disabled={*disabled || *group_disabled}
+                class={classes.clone()}
+                {onchange}
+                {onfocus}
+                {onblur}
+            />
+            <label for={id}>
+                if let Some(children) = children {
+                    {children.clone()}
+                } else {
+                    {value}
+                }
+            </label>
+        </>
+    }
+}
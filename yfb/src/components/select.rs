@@ -3,6 +3,7 @@ use std::rc::Rc;
 use web_sys::HtmlSelectElement;
 use yew::{html::ChildrenRenderer, prelude::*, virtual_dom::VChild};
 
+use super::validity_classes;
 use crate::prelude::*;
 
 #[derive(Clone, PartialEq)]
@@ -48,8 +49,15 @@ pub struct SelectProps<T: Value> {
     pub classes_valid: Classes,
     #[prop_or_default]
     pub classes_invalid: Classes,
+    /// Apply `classes_invalid` once the field has been touched instead of once it is dirty
+    #[prop_or_default]
+    pub invalid_after_touched: bool,
     #[prop_or_default]
     pub onchange: Callback<Event>,
+    #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
 }
 
 #[function_component(Select)]
@@ -62,20 +70,22 @@ pub fn select<T: Value>(
         classes,
         classes_valid,
         classes_invalid,
+        invalid_after_touched,
         children,
         onchange,
+        onfocus,
+        onblur,
     }: &SelectProps<T>,
 ) -> Html {
     let selected = binding.state().value().clone();
     let classes = classes!(
         classes.clone(),
-        binding
-            .state()
-            .dirty()
-            .then(|| match binding.state().valid() {
-                true => classes_valid.clone(),
-                false => classes_invalid.clone(),
-            })
+        validity_classes(
+            &binding.state(),
+            classes_valid,
+            classes_invalid,
+            *invalid_after_touched
+        )
     );
 
     let onchange = {
@@ -90,6 +100,24 @@ pub fn select<T: Value>(
         })
     };
 
+    let onfocus = {
+        let binding = binding.clone();
+
+        onfocus.reform(move |e: FocusEvent| {
+            binding.modifier().focus();
+            e
+        })
+    };
+
+    let onblur = {
+        let binding = binding.clone();
+
+        onblur.reform(move |e: FocusEvent| {
+            binding.modifier().blur();
+            e
+        })
+    };
+
     let autocomplete = if *autocomplete { "on" } else { "off" };
 
     html! {
@@ -101,6 +129,8 @@ pub fn select<T: Value>(
             multiple={*multiple}
             class={classes}
             {onchange}
+            {onfocus}
+            {onblur}
         >
             { for children.iter().map(move |option| {
                 match option {
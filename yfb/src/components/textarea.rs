@@ -1,6 +1,7 @@
 use web_sys::{HtmlTextAreaElement, InputEvent};
 use yew::{html::ImplicitClone, prelude::*};
 
+use super::validity_classes;
 use crate::prelude::*;
 
 #[derive(Clone, Copy, PartialEq)]
@@ -31,6 +32,13 @@ pub struct TextAreaProps {
     pub classes_invalid: Classes,
     #[prop_or_default]
     pub classes_valid: Classes,
+    /// Apply `classes_invalid` once the field has been touched instead of once it is dirty
+    #[prop_or_default]
+    pub invalid_after_touched: bool,
+    #[prop_or_default]
+    pub onfocus: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
     #[prop_or(20)]
     pub cols: u32,
     #[prop_or(5)]
@@ -55,6 +63,9 @@ pub fn text_area(
         classes,
         classes_invalid,
         classes_valid,
+        invalid_after_touched,
+        onfocus,
+        onblur,
         cols,
         rows,
         placeholder,
@@ -66,13 +77,12 @@ pub fn text_area(
 ) -> Html {
     let classes = classes!(
         classes.clone(),
-        binding
-            .state()
-            .dirty()
-            .then(|| match binding.state().valid() {
-                true => classes_valid.clone(),
-                false => classes_invalid.clone(),
-            })
+        validity_classes(
+            &binding.state(),
+            classes_valid,
+            classes_invalid,
+            *invalid_after_touched
+        )
     );
 
     let oninput = {
@@ -86,6 +96,24 @@ pub fn text_area(
         })
     };
 
+    let onfocus = {
+        let binding = binding.clone();
+
+        onfocus.reform(move |e: FocusEvent| {
+            binding.modifier().focus();
+            e
+        })
+    };
+
+    let onblur = {
+        let binding = binding.clone();
+
+        onblur.reform(move |e: FocusEvent| {
+            binding.modifier().blur();
+            e
+        })
+    };
+
     let autocomplete = if *autocomplete { "on" } else { "off" };
     // let spellcheck = spellcheck
     //     .map(|b| if b { "true" } else { "false" })
@@ -103,6 +131,8 @@ pub fn text_area(
             spellcheck={spellcheck.map(|b| b.to_string())}
             {autocomplete}
             {oninput}
+            {onfocus}
+            {onblur}
             disabled={*disabled}
         />
     }
@@ -0,0 +1,156 @@
+#[cfg(feature = "serde_json")]
+use serde_json::{json, Map, Value as Json};
+use yew::AttrValue;
+
+use crate::{
+    field::Field,
+    model::{Dirty, Visit, Visitor},
+};
+
+/// A field that changed relative to its initial value, see [`Binding::changes()`](crate::binding::Binding::changes)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// Binding name of the field, e.g. `order.lines[0].quantity`
+    pub path: String,
+    /// Initial value of the field, `None` when it was added to a list or its variant wasn't active initially
+    pub old: Option<AttrValue>,
+    /// Current value of the field, `None` when it was removed from a list
+    pub new: Option<AttrValue>,
+}
+
+/// Returns the changes of the fields of `state`, whose model is bound under `path`.
+/// Fields are reported when they are dirty, or added to or removed from a list.
+/// Enums report a change of their variant at their own path with the names of the variants.
+pub fn changes(state: &impl Visit, path: &str) -> Vec<FieldChange> {
+    let mut changes = Changes::default();
+    state.visit(path, &mut changes);
+    changes.changes
+}
+
+#[derive(Default)]
+struct Changes {
+    changes: Vec<FieldChange>,
+    /// Paths of the items that were added to lists
+    added: Vec<String>,
+}
+
+impl Changes {
+    fn push(&mut self, path: &str, old: Option<AttrValue>, new: Option<AttrValue>) {
+        self.changes.push(FieldChange {
+            path: path.to_owned(),
+            old,
+            new,
+        });
+    }
+
+    fn is_added(&self, path: &str) -> bool {
+        self.added.iter().any(|item| {
+            path.strip_prefix(item.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+        })
+    }
+}
+
+impl Visitor for Changes {
+    fn field(&mut self, path: &str, field: &Field) {
+        if self.is_added(path) {
+            self.push(path, None, Some(field.value().clone()));
+        } else if field.dirty() {
+            self.push(path, field.initial().cloned(), Some(field.value().clone()));
+        }
+    }
+
+    fn list(&mut self, path: &str, initial_length: usize, length: usize) {
+        self.added
+            .extend((initial_length..length).map(|index| format!("{path}[{index}]")));
+    }
+
+    fn variant(&mut self, path: &str, initial: Option<&str>, current: &str) {
+        if initial != Some(current) {
+            let initial = initial.map(|initial| AttrValue::from(initial.to_owned()));
+            self.push(path, initial, Some(AttrValue::from(current.to_owned())));
+        }
+    }
+
+    fn removed(&mut self, path: &str, field: &Field) {
+        self.push(path, field.initial().cloned(), None);
+    }
+}
+
+/// Returns the [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396) merge patch turning `old` into `new`.
+/// Merge patches can't set a value to `null` and replace arrays as a whole.
+#[cfg(feature = "serde_json")]
+pub fn merge_patch(old: &Json, new: &Json) -> Json {
+    let (Json::Object(old), Json::Object(new)) = (old, new) else {
+        return new.clone();
+    };
+
+    let mut patch = old
+        .keys()
+        .filter(|key| !new.contains_key(*key))
+        .map(|key| (key.clone(), Json::Null))
+        .collect::<Map<_, _>>();
+    for (key, value) in new {
+        match old.get(key) {
+            Some(previous) if previous == value => {}
+            Some(previous @ Json::Object(_)) if value.is_object() => {
+                patch.insert(key.clone(), merge_patch(previous, value));
+            }
+            _ => {
+                patch.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Json::Object(patch)
+}
+
+/// Returns the [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) patch turning `old` into `new`, as an array of operations
+#[cfg(feature = "serde_json")]
+pub fn json_patch(old: &Json, new: &Json) -> Json {
+    let mut operations = Vec::new();
+    diff_json(old, new, "", &mut operations);
+    Json::Array(operations)
+}
+
+#[cfg(feature = "serde_json")]
+fn diff_json(old: &Json, new: &Json, path: &str, operations: &mut Vec<Json>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Json::Object(old), Json::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                let path = format!("{path}/{}", escape(key));
+                operations.push(json!({ "op": "remove", "path": path }));
+            }
+            for (key, value) in new {
+                let path = format!("{path}/{}", escape(key));
+                match old.get(key) {
+                    Some(previous) => diff_json(previous, value, &path, operations),
+                    None => operations.push(json!({ "op": "add", "path": path, "value": value })),
+                }
+            }
+        }
+        (Json::Array(old), Json::Array(new)) => {
+            for (index, (previous, value)) in old.iter().zip(new).enumerate() {
+                diff_json(previous, value, &format!("{path}/{index}"), operations);
+            }
+            // Removing from the end keeps the indices of the remaining items
+            for index in (new.len()..old.len()).rev() {
+                operations.push(json!({ "op": "remove", "path": format!("{path}/{index}") }));
+            }
+            for (index, value) in new.iter().enumerate().skip(old.len()) {
+                let path = format!("{path}/{index}");
+                operations.push(json!({ "op": "add", "path": path, "value": value }));
+            }
+        }
+        _ => operations.push(json!({ "op": "replace", "path": path, "value": new })),
+    }
+}
+
+/// Escapes a key for a JSON pointer
+#[cfg(feature = "serde_json")]
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
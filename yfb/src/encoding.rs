@@ -0,0 +1,130 @@
+use web_sys::{wasm_bindgen::JsValue, FormData};
+use yew::AttrValue;
+
+use crate::{
+    hooks::UseGenerationHandle,
+    model::{AssignError, ModelState, State},
+    validation::FieldError,
+};
+
+/// Returns the name/value pairs of the fields of `model`, keyed by their binding names under the root name `name`,
+/// e.g. `order.lines[0].quantity`. Values are converted with [`Value::to_value()`](crate::model::Value::to_value),
+/// enums add the name of their active variant at their own path.
+pub fn to_pairs<T>(model: &T, name: &str) -> Vec<(String, AttrValue)>
+where
+    T: ModelState,
+{
+    let mut pairs = Vec::new();
+    model.visit_values(name, &mut |key, value| pairs.push((key.to_owned(), value)));
+    pairs
+}
+
+/// Encodes `model` as `application/x-www-form-urlencoded`, see [`to_pairs()`]
+pub fn to_urlencoded<T>(model: &T, name: &str) -> String
+where
+    T: ModelState,
+{
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(to_pairs(model, name))
+        .finish()
+}
+
+/// Encodes `model` as `FormData`, e.g. for posting it with `fetch`, see [`to_pairs()`].
+/// Only available in the browser.
+pub fn to_form_data<T>(model: &T, name: &str) -> Result<FormData, JsValue>
+where
+    T: ModelState,
+{
+    let form_data = FormData::new()?;
+    for (key, value) in to_pairs(model, name) {
+        form_data.append_with_str(&key, &value)?;
+    }
+    Ok(form_data)
+}
+
+/// A model decoded from name/value pairs together with its state, see [`from_pairs()`]
+#[derive(Debug)]
+pub struct Decoded<T>
+where
+    T: ModelState,
+{
+    pub model: T,
+    /// State of the model with the decoded values as its initial values.
+    /// It isn't attached to any component yet, use [`Binding::restore()`](crate::binding::Binding::restore) to bind it.
+    pub state: T::State,
+    /// Pairs whose value could not be parsed, by the binding name of their field.
+    /// The field keeps the value it had before.
+    pub errors: Vec<(String, FieldError)>,
+}
+
+impl<T> Decoded<T>
+where
+    T: ModelState,
+{
+    /// Returns whether every value could be parsed
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Decodes the pairs whose key starts with `name` into `model`, the reverse of [`to_pairs()`].
+/// Fields without a pair keep the value they have in `model`, pairs that don't match any field are ignored.
+pub fn from_pairs<T, K, V>(
+    mut model: T,
+    name: &str,
+    pairs: impl IntoIterator<Item = (K, V)>,
+) -> Decoded<T>
+where
+    T: ModelState,
+    K: AsRef<str>,
+    V: Into<AttrValue>,
+{
+    let errors = assign_pairs(&mut model, name, pairs);
+    let state = State::create(&model, true, UseGenerationHandle::default());
+    Decoded {
+        model,
+        state,
+        errors,
+    }
+}
+
+/// Decodes `input` in `application/x-www-form-urlencoded` into `model`, see [`from_pairs()`]
+pub fn from_urlencoded<T>(model: T, name: &str, input: &str) -> Decoded<T>
+where
+    T: ModelState,
+{
+    from_pairs(
+        model,
+        name,
+        form_urlencoded::parse(input.as_bytes()).map(|(key, value)| (key, value.into_owned())),
+    )
+}
+
+/// Assigns the pairs whose key starts with `name` to `model`, returning the errors of the values that could not be parsed
+pub(crate) fn assign_pairs<T, K, V>(
+    model: &mut T,
+    name: &str,
+    pairs: impl IntoIterator<Item = (K, V)>,
+) -> Vec<(String, FieldError)>
+where
+    T: ModelState,
+    K: AsRef<str>,
+    V: Into<AttrValue>,
+{
+    pairs
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_ref();
+            match model.assign_value(strip_name(key, name)?, &value.into()) {
+                Err(AssignError::InvalidValue(error)) => Some((key.to_owned(), error)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the path of `key` relative to the model named `name`, if `key` belongs to it
+pub(crate) fn strip_name<'a>(key: &'a str, name: &str) -> Option<&'a str> {
+    key.strip_prefix(name)
+        .filter(|path| path.is_empty() || path.starts_with(['.', '[']))
+}
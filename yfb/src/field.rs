@@ -1,21 +1,73 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    future::Future,
+    time::Duration,
+};
 
-use yew::AttrValue;
+use yew::{
+    platform::{spawn_local, time::sleep},
+    AttrValue,
+};
 
 use crate::{
+    format::{EnglishFormatter, MessageFormatter},
+    history::Change,
     hooks::UseGenerationHandle,
-    model::{Dirty, State, Value},
+    model::{Dirty, SetErrors, State, Touched, Validating, Value, Visit, Visitor},
     modifier::{BaseModifier, Modifier},
     state_model::StateModelRc,
+    validation::{FieldError, ValidationErrorsKind},
 };
 
+/// Code of the [`FieldError`] reported for a field whose asynchronous validator is still pending
+pub const VALIDATING_CODE: &str = "validating";
+
+/// Code of the [`FieldError`] for raw input that could not be parsed, with the params `value` and `type`
+pub const PARSE_CODE: &str = "parse";
+
+/// Code of the [`FieldError`] holding a message set through [`Modifier::set_message()`]
+pub const CUSTOM_CODE: &str = "custom";
+
+#[doc(hidden)]
+/// Returns the error with code [`PARSE_CODE`] for `value` that could not be parsed into `T`
+pub fn parse_error<T>(value: impl Into<AttrValue>) -> FieldError {
+    FieldError::new(PARSE_CODE)
+        .with_param("value", value)
+        .with_param("type", std::any::type_name::<T>())
+}
+
+/// Progress of the asynchronous validator of a field for its current value
+#[derive(Debug, Clone, PartialEq)]
+enum AsyncValidation {
+    Unchecked,
+    /// Holds the generation the validator was started at, results of other generations are stale
+    Pending(usize),
+    Checked,
+}
+
+/// Whether the value of a debounced field stopped changing, see [`FieldModifier::debounce()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Debounce {
+    Settled,
+    Changed,
+    /// Holds the generation of the value the timer was started for
+    Pending(usize),
+}
+
 /// Contains the current state of a [`Model`](crate::model::Model)'s field
 #[derive(PartialEq)]
 pub struct Field {
     initial: Option<AttrValue>,
     value: AttrValue,
-    message: Option<AttrValue>,
-    error: Option<AttrValue>,
+    // The error shown as message, messages are formatted when they are read
+    message: Option<FieldError>,
+    errors: Vec<FieldError>,
+    parse_error: Option<FieldError>,
+    touched: bool,
+    focused: bool,
+    async_validation: AsyncValidation,
+    async_error: Option<FieldError>,
+    debounce: Debounce,
     generation: usize,
     generation_handle: UseGenerationHandle,
 }
@@ -25,18 +77,81 @@ impl fmt::Debug for Field {
         f.debug_struct("Field")
             .field("initial", &self.initial.as_ref().map(|i| i.as_str()))
             .field("value", &self.value.as_str())
-            .field("message", &self.message.as_ref().map(|m| m.as_str()))
-            .field("error", &self.error.as_ref().map(|e| e.as_str()))
+            .field("message", &self.message)
+            .field("errors", &self.errors)
+            .field("parse_error", &self.parse_error)
+            .field("touched", &self.touched)
+            .field("focused", &self.focused)
+            .field("async_validation", &self.async_validation)
+            .field("debounce", &self.debounce)
             .field("generation", &self.generation)
             .field("generation_handle", &self.generation_handle)
             .finish()
     }
 }
 
+/// Serialized form of a [`Field`], leaving out what only applies while the component is mounted like focus and
+/// pending asynchronous validators
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Field")]
+struct FieldData {
+    initial: Option<String>,
+    value: String,
+    message: Option<FieldError>,
+    errors: Vec<FieldError>,
+    parse_error: Option<FieldError>,
+    touched: bool,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Field {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        FieldData {
+            initial: self.initial.as_ref().map(|initial| initial.to_string()),
+            value: self.value.to_string(),
+            message: self.message.clone(),
+            errors: self.errors.clone(),
+            parse_error: self.parse_error.clone(),
+            touched: self.touched,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = FieldData::deserialize(deserializer)?;
+        let generation = UseGenerationHandle::default();
+
+        Ok(Field {
+            initial: data.initial.map(AttrValue::from),
+            value: data.value.into(),
+            message: data.message,
+            errors: data.errors,
+            parse_error: data.parse_error,
+            touched: data.touched,
+            focused: false,
+            async_validation: AsyncValidation::Unchecked,
+            async_error: None,
+            debounce: Debounce::Settled,
+            generation: generation.generation(),
+            generation_handle: generation,
+        })
+    }
+}
+
 impl Field {
-    /// Returns whether the field passed validation
+    /// Returns whether the field passed validation, including its asynchronous validator if it completed
     pub fn valid(&self) -> bool {
-        self.message.is_none()
+        self.message.is_none() && self.async_error.is_none()
     }
 
     /// Returns the current value of the field
@@ -44,6 +159,11 @@ impl Field {
         &self.value
     }
 
+    /// Returns the value the field is compared to for [`Dirty`], `None` for fields added after the state was created
+    pub fn initial(&self) -> Option<&AttrValue> {
+        self.initial.as_ref()
+    }
+
     pub(crate) fn set_value(&mut self, value: AttrValue, replace: bool) {
         if value != self.value {
             if replace {
@@ -55,20 +175,49 @@ impl Field {
                     Some(initial) if *initial == value => {
                         self.value = initial.clone();
                         self.message = None;
+                        self.errors.clear();
                     }
                     _ => self.value = value,
                 }
             }
+            // Any pending result of the asynchronous validator is stale from now on
+            self.async_validation = AsyncValidation::Unchecked;
+            self.async_error = None;
+            self.debounce = Debounce::Changed;
             self.generation = self.generation_handle.increase();
         }
     }
 
     /// Returns `Some(..)` if the field did not pass validation, or else `None`
-    pub fn message(&self) -> Option<&AttrValue> {
-        self.message.as_ref()
+    /// Messages are formatted by the [`EnglishFormatter`], see [`format_message()`](Self::format_message) for other formatters.
+    pub fn message(&self) -> Option<AttrValue> {
+        self.format_message(&EnglishFormatter)
+    }
+
+    /// Returns the message of the field like [`message()`](Self::message), formatted by `formatter`
+    pub fn format_message(&self, formatter: &dyn MessageFormatter) -> Option<AttrValue> {
+        self.message
+            .as_ref()
+            .or(self.async_error.as_ref())
+            .map(|error| formatter.format(error))
     }
 
-    pub(crate) fn set_message(&mut self, message: Option<AttrValue>) {
+    /// Returns all errors of the field, including the error of its asynchronous validator
+    pub fn errors(&self) -> impl Iterator<Item = &FieldError> {
+        self.errors.iter().chain(&self.async_error)
+    }
+
+    /// Returns the errors of the field with `code`, e.g. to render which rules of a password checklist fail
+    pub fn errors_for<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a FieldError> {
+        self.errors().filter(move |error| error.code() == code)
+    }
+
+    /// Returns whether the field has an error with `code`
+    pub fn has_error(&self, code: &str) -> bool {
+        self.errors_for(code).next().is_some()
+    }
+
+    pub(crate) fn set_message(&mut self, message: Option<FieldError>) {
         if self.message != message {
             self.message = message;
             self.generation = self.generation_handle.increase();
@@ -76,13 +225,26 @@ impl Field {
     }
 
     /// Returns `Some(..)` when the field was unable to parse the raw string input, or else `None`
-    pub fn error(&self) -> Option<&AttrValue> {
-        self.error.as_ref()
+    /// Errors are formatted by the [`EnglishFormatter`], see [`format_error()`](Self::format_error) for other formatters.
+    pub fn error(&self) -> Option<AttrValue> {
+        self.format_error(&EnglishFormatter)
+    }
+
+    /// Returns the error of the field like [`error()`](Self::error), formatted by `formatter`
+    pub fn format_error(&self, formatter: &dyn MessageFormatter) -> Option<AttrValue> {
+        self.parse_error
+            .as_ref()
+            .map(|error| formatter.format(error))
+    }
+
+    /// Returns the structured error for raw input that could not be parsed, with code [`PARSE_CODE`]
+    pub fn parse_error(&self) -> Option<&FieldError> {
+        self.parse_error.as_ref()
     }
 
-    pub(crate) fn set_error(&mut self, error: Option<AttrValue>) {
-        if self.error != error {
-            self.error = error;
+    pub(crate) fn set_error(&mut self, error: Option<FieldError>) {
+        if self.parse_error != error {
+            self.parse_error = error;
             self.generation = self.generation_handle.increase();
         }
     }
@@ -91,6 +253,46 @@ impl Field {
         self.initial = initial;
         self.generation = self.generation_handle.increase();
     }
+
+    /// Marks the asynchronous validator as pending, unless the current value was already checked or is being checked.
+    /// Returns the generation to finish the validation with.
+    fn start_validating(&mut self) -> Option<usize> {
+        match self.async_validation {
+            AsyncValidation::Unchecked => {
+                let generation = self.generation_handle.increase();
+                self.async_validation = AsyncValidation::Pending(generation);
+                self.generation = generation;
+                Some(generation)
+            }
+            _ => None,
+        }
+    }
+
+    /// Stores the result of the asynchronous validator, the result is dropped when the value changed since it was started
+    fn finish_validating(&mut self, generation: usize, error: Option<FieldError>) {
+        if self.async_validation == AsyncValidation::Pending(generation) {
+            self.async_error = error;
+            self.async_validation = AsyncValidation::Checked;
+            self.generation = self.generation_handle.increase();
+        }
+    }
+
+    /// Marks the value as settled when it has not changed since the debounce timer for `generation` was started
+    fn settle(&mut self, generation: usize) -> bool {
+        let settled = self.debounce == Debounce::Pending(generation);
+        if settled {
+            self.debounce = Debounce::Settled;
+        }
+        settled
+    }
+
+    pub(crate) fn set_focused(&mut self, focused: bool) {
+        if self.focused != focused || (!focused && !self.touched) {
+            self.focused = focused;
+            self.touched |= !focused;
+            self.generation = self.generation_handle.increase();
+        }
+    }
 }
 
 impl<T> State<T> for Field
@@ -105,8 +307,14 @@ where
             value,
             generation: generation.generation(),
             generation_handle: generation,
-            message: Default::default(),
-            error: Default::default(),
+            message: None,
+            errors: Vec::new(),
+            parse_error: None,
+            touched: false,
+            focused: false,
+            async_validation: AsyncValidation::Unchecked,
+            async_error: None,
+            debounce: Debounce::Settled,
         }
     }
 
@@ -117,6 +325,27 @@ where
     fn generation(&self) -> usize {
         self.generation
     }
+
+    fn attach(&mut self, generation: UseGenerationHandle) {
+        self.generation = generation.generation();
+        self.generation_handle = generation;
+    }
+
+    fn restore_initial(&self, model: &mut T) -> bool {
+        let Some(initial) = self.initial.as_ref() else {
+            return false;
+        };
+        if let Ok(value) = T::from_value(initial) {
+            *model = value;
+        }
+        true
+    }
+
+    fn initial_model(&self) -> Option<T> {
+        self.initial
+            .as_ref()
+            .and_then(|initial| T::from_value(initial).ok())
+    }
 }
 
 impl Dirty for Field {
@@ -128,6 +357,65 @@ impl Dirty for Field {
     }
 }
 
+impl Visit for Field {
+    fn visit(&self, path: &str, visitor: &mut dyn Visitor) {
+        visitor.field(path, self);
+    }
+}
+
+impl Touched for Field {
+    fn touched(&self) -> bool {
+        self.touched
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    fn touch(&mut self) {
+        if !self.touched {
+            self.touched = true;
+            self.generation = self.generation_handle.increase();
+        }
+    }
+}
+
+impl SetErrors for Field {
+    fn set_errors(&mut self, errors: Option<&ValidationErrorsKind>, all: bool) {
+        let errors = match errors {
+            Some(ValidationErrorsKind::Field(errors)) => errors.clone(),
+            _ => Vec::new(),
+        };
+
+        // Messages that are already shown are kept until the field becomes valid
+        if errors.is_empty() || all || self.dirty() {
+            let message = errors.first().cloned();
+            if self.errors != errors {
+                self.errors = errors;
+                self.generation = self.generation_handle.increase();
+            }
+            self.set_message(message);
+        }
+    }
+
+    fn clear_errors(&mut self) {
+        self.set_errors(None, true);
+        self.set_error(None);
+        if self.async_validation != AsyncValidation::Unchecked {
+            // The asynchronous validator runs again with the next validation
+            self.async_validation = AsyncValidation::Unchecked;
+            self.async_error = None;
+            self.generation = self.generation_handle.increase();
+        }
+    }
+}
+
+impl Validating for Field {
+    fn validating(&self) -> bool {
+        matches!(self.async_validation, AsyncValidation::Pending(_))
+    }
+}
+
 impl Display for Field {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.value)
@@ -152,7 +440,8 @@ where
 
     fn set_message(&self, message: Option<AttrValue>) {
         let (_, mut state) = self.state_model().as_mut();
-        state.set_message(message);
+        state
+            .set_message(message.map(|message| FieldError::new(CUSTOM_CODE).with_message(message)));
     }
 }
 
@@ -161,25 +450,24 @@ where
     T: Value,
 {
     pub fn set(&self, value: impl Into<AttrValue>) {
-        let (mut model, mut state) = self.state_model().as_mut();
         let value = value.into();
 
-        match T::from_value(&value) {
-            Ok(t) => {
-                *model = t;
-                state.set_value(value, false);
-            }
-            Err(_) => {
-                state.set_error(Some(
-                    format!(
-                        "Invalid value '{}' for type {}",
-                        value,
-                        std::any::type_name::<T>()
-                    )
-                    .into(),
-                ));
+        {
+            let (mut model, mut state) = self.state_model().as_mut();
+            match T::from_value(&value) {
+                Ok(t) => {
+                    *model = t;
+                    state.set_value(value.clone(), false);
+                    state.set_error(None);
+                }
+                Err(_) => {
+                    state.set_error(Some(parse_error::<T>(value)));
+                    return;
+                }
             }
         }
+
+        self.state_model().record(Change::Typing(value));
     }
 
     pub fn set_value(&self, value: T) {
@@ -192,4 +480,83 @@ where
         let (_, mut state) = self.state_model().as_mut();
         state.set_initial(value.map(|v| v.to_value()));
     }
+
+    #[doc(hidden)]
+    /// Runs the asynchronous `validator` with the current value, unless that value was already checked or is being checked.
+    /// Used by the derived modifiers for fields with `#[yfb(async_validator = "..")]`.
+    pub fn validate_async<F, Fut, E>(&self, validator: F)
+    where
+        T: Clone,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<(), E>> + 'static,
+        E: Into<FieldError>,
+    {
+        let (generation, value) = {
+            let (model, mut state) = self.state_model().as_mut();
+            match state.start_validating() {
+                Some(generation) => (generation, model.clone()),
+                None => return,
+            }
+        };
+
+        let future = validator(value);
+        let state_model = self.state_model().clone();
+        spawn_local(async move {
+            let result = future.await;
+            let (_, mut state) = state_model.as_mut();
+            state.finish_validating(generation, result.err().map(Into::into));
+        });
+    }
+
+    #[doc(hidden)]
+    /// Returns whether the value has not changed for `debounce_ms`.
+    /// Otherwise a timer is started for the current value, `revalidate` is called when it expires before the value changes again.
+    /// Used by the derived modifiers for fields with `#[yfb(debounce_ms = ..)]`.
+    pub fn debounce(&self, debounce_ms: u64, revalidate: impl FnOnce() + 'static) -> bool {
+        let generation = {
+            let (_, mut state) = self.state_model().as_mut();
+            match state.debounce {
+                Debounce::Settled => return true,
+                Debounce::Pending(_) => return false,
+                Debounce::Changed => {
+                    state.debounce = Debounce::Pending(state.generation);
+                    state.generation
+                }
+            }
+        };
+
+        let state_model = self.state_model().clone();
+        spawn_local(async move {
+            sleep(Duration::from_millis(debounce_ms)).await;
+            let settled = state_model.as_mut().1.settle(generation);
+            if settled {
+                revalidate();
+            }
+        });
+
+        false
+    }
+
+    #[doc(hidden)]
+    /// Returns the error of the asynchronous validator, or a [`VALIDATING_CODE`] error while it is pending
+    pub fn async_error(&self) -> Option<FieldError> {
+        let state = self.state();
+        match &state.async_validation {
+            AsyncValidation::Unchecked => None,
+            AsyncValidation::Pending(_) => Some(FieldError::new(VALIDATING_CODE)),
+            AsyncValidation::Checked => state.async_error.clone(),
+        }
+    }
+
+    /// Marks the field as focused, e.g. when its input receives focus
+    pub fn focus(&self) {
+        self.state_model().as_mut().1.set_focused(true);
+        self.state_model().record(Change::Boundary);
+    }
+
+    /// Marks the field as no longer focused and as touched, e.g. when its input loses focus
+    pub fn blur(&self) {
+        self.state_model().as_mut().1.set_focused(false);
+        self.state_model().record(Change::Boundary);
+    }
 }
@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    ops::Deref,
+    rc::Rc,
+    str::FromStr,
+};
+
+use yew::prelude::*;
+
+use crate::validation::FieldError;
+
+/// Turns a [`FieldError`] into the text shown to the user
+pub trait MessageFormatter {
+    fn format(&self, error: &FieldError) -> AttrValue;
+}
+
+/// Formats errors in English.
+/// The message an error was declared with is used as is, otherwise a message is derived from its code and params.
+/// Errors with a custom code and no message are shown by their code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnglishFormatter;
+
+impl MessageFormatter for EnglishFormatter {
+    fn format(&self, error: &FieldError) -> AttrValue {
+        if let Some(message) = error.message() {
+            return message.clone();
+        }
+
+        let param = |name| error.param(name);
+        let bounds = |unit: &str| match (param("equal"), param("min"), param("max")) {
+            (Some(equal), _, _) => format!("Must be exactly {equal}{unit}"),
+            (None, Some(min), Some(max)) => format!("Must be between {min} and {max}{unit}"),
+            (None, Some(min), None) => format!("Must be at least {min}{unit}"),
+            (None, None, Some(max)) => format!("Must be at most {max}{unit}"),
+            (None, None, None) => "Invalid value".to_owned(),
+        };
+
+        let message = match error.code().as_str() {
+            "length" => bounds(" characters long"),
+            "range" => bounds(""),
+            "email" => "Must be a valid email address".to_owned(),
+            "url" => "Must be a valid URL".to_owned(),
+            "phone" => "Must be a valid phone number".to_owned(),
+            "credit_card" => "Must be a valid credit card number".to_owned(),
+            "must_match" => match param("other") {
+                Some(other) => format!("Must match {other}"),
+                None => "Must match".to_owned(),
+            },
+            "contains" => match param("needle") {
+                Some(needle) => format!("Must contain '{needle}'"),
+                None => "Invalid value".to_owned(),
+            },
+            "does_not_contain" => match param("needle") {
+                Some(needle) => format!("Must not contain '{needle}'"),
+                None => "Invalid value".to_owned(),
+            },
+            "regex" => "Has an invalid format".to_owned(),
+            "non_control_character" => "Must not contain control characters".to_owned(),
+            "required" => "Required".to_owned(),
+            crate::field::PARSE_CODE => match param("value") {
+                Some(value) => format!("Invalid value '{value}'"),
+                None => "Invalid value".to_owned(),
+            },
+            crate::field::VALIDATING_CODE => "Validating...".to_owned(),
+            code => code.to_owned(),
+        };
+
+        message.into()
+    }
+}
+
+/// A catalog of message templates, e.g. for translations.
+///
+/// Templates are looked up by the message an error was declared with first, then by its code.
+/// Params of the error are inserted for `{name}` placeholders.
+/// Errors without a template are formatted by the fallback, [`EnglishFormatter`] by default.
+///
+/// Catalogs can be parsed from lines of `key = template`, lines starting with `#` are ignored:
+/// ```
+/// # use yfb::format::Catalog;
+/// let catalog: Catalog = r#"
+///     ## Dutch
+///     length = Moet tussen {min} en {max} tekens lang zijn
+///     required = Verplicht
+/// "#
+/// .parse()
+/// .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Catalog {
+    templates: HashMap<String, String>,
+    fallback: SharedFormatter,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self {
+            templates: HashMap::new(),
+            fallback: SharedFormatter::default(),
+        }
+    }
+
+    /// Adds the template for `key`, replacing any previous template
+    pub fn with(mut self, key: impl Into<String>, template: impl Into<String>) -> Self {
+        self.templates.insert(key.into(), template.into());
+        self
+    }
+
+    /// Sets the formatter for errors without a template
+    pub fn with_fallback(mut self, fallback: impl Into<SharedFormatter>) -> Self {
+        self.fallback = fallback.into();
+        self
+    }
+
+    /// Returns the template for `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.templates.get(key).map(String::as_str)
+    }
+
+    fn render(template: &str, error: &FieldError) -> String {
+        error
+            .params()
+            .iter()
+            .fold(template.to_owned(), |message, (name, value)| {
+                message.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageFormatter for Catalog {
+    fn format(&self, error: &FieldError) -> AttrValue {
+        error
+            .message()
+            .and_then(|message| self.get(message))
+            .or_else(|| self.get(error.code()))
+            .map(|template| Self::render(template, error).into())
+            .unwrap_or_else(|| self.fallback.format(error))
+    }
+}
+
+/// Error parsing a [`Catalog`], holding the line that is neither a comment nor a `key = template` pair
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCatalogError {
+    pub line: usize,
+}
+
+impl Display for ParseCatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected `key = template` on line {}", self.line)
+    }
+}
+
+impl std::error::Error for ParseCatalogError {}
+
+impl FromStr for Catalog {
+    type Err = ParseCatalogError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        source
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .try_fold(Catalog::new(), |catalog, (line, entry)| {
+                match entry.split_once('=') {
+                    Some((key, template)) if !key.trim().is_empty() => {
+                        Ok(catalog.with(key.trim(), template.trim()))
+                    }
+                    _ => Err(ParseCatalogError { line }),
+                }
+            })
+    }
+}
+
+/// A shared [`MessageFormatter`], provided to components through a `ContextProvider<SharedFormatter>` or set on a binding
+/// with [`Binding::with_formatter()`](crate::binding::Binding::with_formatter)
+#[derive(Clone)]
+pub struct SharedFormatter(Rc<dyn MessageFormatter>);
+
+impl SharedFormatter {
+    pub fn new(formatter: impl MessageFormatter + 'static) -> Self {
+        Self(Rc::new(formatter))
+    }
+}
+
+impl Default for SharedFormatter {
+    fn default() -> Self {
+        Self::new(EnglishFormatter)
+    }
+}
+
+impl<F> From<F> for SharedFormatter
+where
+    F: MessageFormatter + 'static,
+{
+    fn from(formatter: F) -> Self {
+        Self::new(formatter)
+    }
+}
+
+impl Deref for SharedFormatter {
+    type Target = dyn MessageFormatter;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for SharedFormatter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SharedFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedFormatter(..)")
+    }
+}
+
+/// Returns the [`SharedFormatter`] provided through context, or the [`EnglishFormatter`] when there is none
+#[hook]
+pub fn use_formatter() -> SharedFormatter {
+    use_context::<SharedFormatter>().unwrap_or_default()
+}
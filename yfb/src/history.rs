@@ -0,0 +1,200 @@
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    mem,
+    rc::Rc,
+};
+
+use yew::AttrValue;
+
+use crate::{
+    model::{ModelState, State},
+    modifier::Modifier,
+    state_model::{StateModel, StateModelRc},
+};
+
+/// Decides which changes of a model with history are merged into a single step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Coalesce {
+    /// Every change is a step of its own
+    Never,
+    /// Typing into a field is merged into one step per word, a word ends with whitespace or when the field loses focus
+    #[default]
+    Words,
+    /// Typing into a field is merged into one step until the field loses focus
+    Focus,
+}
+
+/// Describes a change to the history of a model, reported by modifiers through [`StateModel::record()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The value of a field was set to the given text, e.g. while the user is typing
+    Typing(AttrValue),
+    /// Ends the current step, e.g. when a field gains or loses focus
+    Boundary,
+}
+
+/// Undo and redo of the model bound by a [`Binding`](crate::binding::Binding), independent of the model's type
+pub(crate) trait History {
+    fn undo(self: Rc<Self>) -> bool;
+    fn redo(self: Rc<Self>) -> bool;
+    fn can_undo(&self) -> bool;
+    fn can_redo(&self) -> bool;
+}
+
+struct Steps<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// The model as of the latest step
+    recorded: T,
+    /// Whether typing may still be merged into the latest step
+    open: bool,
+}
+
+/// Root state model that records snapshots of the model whenever it changed, see
+/// [`use_binding_with_history()`](fn@crate::hooks::use_binding_with_history).
+///
+/// Changes are detected lazily, at the next access to the model through a modifier or when reported through [`StateModel::record()`].
+pub struct HistoryStateModel<T>
+where
+    T: ModelState + Clone,
+{
+    inner: RefCell<(T, T::State)>,
+    steps: RefCell<Steps<T>>,
+    coalesce: Coalesce,
+}
+
+impl<T> HistoryStateModel<T>
+where
+    T: ModelState + Clone,
+{
+    pub fn new(model: T, state: T::State, coalesce: Coalesce) -> Self {
+        Self {
+            steps: RefCell::new(Steps {
+                undo: Vec::new(),
+                redo: Vec::new(),
+                recorded: model.clone(),
+                open: false,
+            }),
+            inner: RefCell::new((model, state)),
+            coalesce,
+        }
+    }
+
+    /// Records the model as a new step if it changed since the latest step, `change` tells how it was changed
+    fn checkpoint(&self, change: Option<&Change>) {
+        let model = self.inner.model();
+        let mut steps = self.steps.borrow_mut();
+
+        if *model == steps.recorded {
+            if change == Some(&Change::Boundary) {
+                steps.open = false;
+            }
+            return;
+        }
+
+        let typing = match change {
+            Some(Change::Typing(value)) => Some(value),
+            _ => None,
+        };
+        if typing.is_some() && steps.open {
+            steps.recorded = model.clone();
+        } else {
+            let previous = mem::replace(&mut steps.recorded, model.clone());
+            steps.undo.push(previous);
+        }
+        steps.redo.clear();
+        steps.open = match (self.coalesce, typing) {
+            (Coalesce::Never, _) | (_, None) => false,
+            (Coalesce::Words, Some(value)) => !value.ends_with(char::is_whitespace),
+            (Coalesce::Focus, Some(_)) => true,
+        };
+    }
+
+    /// Restores the model to the step taken by `pop`, the current model is handed to `push`
+    fn restore(
+        self: Rc<Self>,
+        pop: fn(&mut Steps<T>) -> Option<T>,
+        push: fn(&mut Steps<T>, T),
+    ) -> bool {
+        self.checkpoint(None);
+
+        let model = {
+            let mut steps = self.steps.borrow_mut();
+            let Some(model) = pop(&mut steps) else {
+                return false;
+            };
+            let current = mem::replace(&mut steps.recorded, model.clone());
+            push(&mut steps, current);
+            steps.open = false;
+            model
+        };
+
+        {
+            let (mut model_mut, mut state) = self.inner.as_mut();
+            *model_mut = model;
+            State::<T>::update(&mut *state, &*model_mut, false);
+        }
+
+        let state_model: StateModelRc<T> = self;
+        let _ = <T::Modifier as Modifier<T>>::create(state_model).validate(false);
+        true
+    }
+}
+
+impl<T> StateModel for HistoryStateModel<T>
+where
+    T: ModelState + Clone,
+{
+    type Model = T;
+
+    fn model(&self) -> Ref<'_, Self::Model> {
+        self.inner.model()
+    }
+
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State> {
+        self.inner.state()
+    }
+
+    fn as_mut(
+        &self,
+    ) -> (
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
+    ) {
+        self.checkpoint(None);
+        self.inner.as_mut()
+    }
+
+    fn record(&self, change: Change) {
+        self.checkpoint(Some(&change));
+    }
+}
+
+impl<T> History for HistoryStateModel<T>
+where
+    T: ModelState + Clone,
+{
+    fn undo(self: Rc<Self>) -> bool {
+        self.restore(
+            |steps| steps.undo.pop(),
+            |steps, model| steps.redo.push(model),
+        )
+    }
+
+    fn redo(self: Rc<Self>) -> bool {
+        self.restore(
+            |steps| steps.redo.pop(),
+            |steps, model| steps.undo.push(model),
+        )
+    }
+
+    fn can_undo(&self) -> bool {
+        self.checkpoint(None);
+        !self.steps.borrow().undo.is_empty()
+    }
+
+    fn can_redo(&self) -> bool {
+        self.checkpoint(None);
+        !self.steps.borrow().redo.is_empty()
+    }
+}
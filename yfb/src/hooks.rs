@@ -1,10 +1,21 @@
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    future::{poll_fn, Future},
+    rc::Rc,
+    task::{Poll, Waker},
+};
 
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 use yew::prelude::*;
 
+#[cfg(feature = "serde")]
+use crate::persist::{load_draft, DraftStorage};
 use crate::{
     binding::Binding,
+    history::{Coalesce, HistoryStateModel},
     model::{Model, ModelState},
+    query::{load_query, UrlQuery},
 };
 
 /// Get a binding of model `T`
@@ -68,13 +79,169 @@ where
         )
     };
 
-    Binding::new(state_model, name, generation.generation())
+    Binding::new(state_model, name, generation)
+}
+
+/// Get a binding of model `T` that records the changes of the model, allowing to [`undo()`](Binding::undo) and [`redo()`](Binding::redo) them.
+/// `coalesce` decides which changes are merged into a single step.
+#[cfg_attr(not(test), hook)]
+pub fn use_binding_with_history<T>(init_fn: impl FnOnce() -> T, coalesce: Coalesce) -> Binding<T>
+where
+    T: Model + Clone + 'static,
+{
+    let generation = use_generation();
+    #[cfg(not(test))]
+    let state_model = {
+        let generation = generation.clone();
+        use_memo(
+            move |_| {
+                let model = init_fn();
+                let state = crate::model::State::create(&model, true, generation);
+                HistoryStateModel::new(model, state, coalesce)
+            },
+            (),
+        )
+    };
+    #[cfg(test)]
+    let state_model = {
+        let model = init_fn();
+        let state = crate::model::State::create(&model, true, generation.clone());
+        Rc::new(HistoryStateModel::new(model, state, coalesce))
+    };
+
+    Binding::new(state_model.clone(), T::NAME, generation).with_history(state_model)
+}
+
+/// Get a binding of model `T` that is kept in sync with the query string of the page's URL, making it shareable as a link.
+/// Values are keyed by their binding names, e.g. `filter.status=open&filter.tags[0]=a`.
+/// The query is parsed into the model created by `init_fn` when the component is mounted, and rewritten whenever the model changes.
+#[cfg_attr(not(test), hook)]
+pub fn use_query_binding<T>(init_fn: impl FnOnce() -> T) -> Binding<T>
+where
+    T: Model + 'static,
+{
+    use_query_binding_with_url(crate::query::LocationQuery, init_fn)
+}
+
+/// Like [`use_query_binding()`](fn@use_query_binding), but reads and writes the query through `url`
+#[cfg_attr(not(test), hook)]
+pub fn use_query_binding_with_url<T, U>(url: U, init_fn: impl FnOnce() -> T) -> Binding<T>
+where
+    T: Model + 'static,
+    U: UrlQuery + 'static,
+{
+    let generation = use_generation();
+    let init = {
+        let generation = generation.clone();
+        let url = &url;
+        move || {
+            let mut model = init_fn();
+            load_query(url, T::NAME, &mut model);
+            let state = crate::model::State::create(&model, true, generation);
+            RefCell::new((model, state))
+        }
+    };
+    #[cfg(not(test))]
+    let state_model = use_memo(move |_| init(), ());
+    #[cfg(test)]
+    let state_model = Rc::new(init());
+
+    let binding = Binding::new(state_model, T::NAME, generation.clone());
+    #[cfg(not(test))]
+    {
+        let binding = binding.clone();
+        use_effect_with_deps(
+            move |_| crate::query::save_query(&url, &binding),
+            generation.generation(),
+        );
+    }
+    binding
+}
+
+/// Get a binding of model `T` that is kept as a draft in `localStorage` under `key`, so it survives a reload of the page.
+/// A draft stored earlier is loaded when the component is mounted, otherwise the model is created with `init_fn`.
+/// The model and its state are written back whenever they change.
+#[cfg(feature = "serde")]
+#[cfg_attr(not(test), hook)]
+pub fn use_persisted_binding<T>(
+    key: impl Into<AttrValue>,
+    init_fn: impl FnOnce() -> T,
+) -> Binding<T>
+where
+    T: Model + Serialize + DeserializeOwned + 'static,
+    T::State: Serialize + DeserializeOwned,
+{
+    use_persisted_binding_with_storage(key, crate::persist::LocalStorage, init_fn)
+}
+
+/// Like [`use_persisted_binding()`](fn@use_persisted_binding), but keeps the draft in `storage`.
+/// Remove the draft from the storage once it isn't needed anymore, e.g. after the model was submitted.
+#[cfg(feature = "serde")]
+#[cfg_attr(not(test), hook)]
+pub fn use_persisted_binding_with_storage<T, S>(
+    key: impl Into<AttrValue>,
+    storage: S,
+    init_fn: impl FnOnce() -> T,
+) -> Binding<T>
+where
+    T: Model + Serialize + DeserializeOwned + 'static,
+    T::State: Serialize + DeserializeOwned,
+    S: DraftStorage + 'static,
+{
+    let key = key.into();
+    let generation = use_generation();
+    let init = {
+        let generation = generation.clone();
+        let (key, storage) = (&key, &storage);
+        move || {
+            let (model, state) = match load_draft::<T>(storage, key) {
+                Some((model, mut state)) => {
+                    crate::model::State::<T>::attach(&mut state, generation);
+                    (model, state)
+                }
+                None => {
+                    let model = init_fn();
+                    let state = crate::model::State::create(&model, true, generation);
+                    (model, state)
+                }
+            };
+            RefCell::new((model, state))
+        }
+    };
+    #[cfg(not(test))]
+    let state_model = use_memo(move |_| init(), ());
+    #[cfg(test)]
+    let state_model = Rc::new(init());
+
+    let binding = Binding::new(state_model, T::NAME, generation.clone());
+    #[cfg(not(test))]
+    {
+        let binding = binding.clone();
+        use_effect_with_deps(
+            move |_| crate::persist::save_draft(&storage, &key, &binding),
+            generation.generation(),
+        );
+    }
+    binding
 }
 
 #[derive(Clone)]
 pub struct UseGenerationHandle {
     generation: Rc<Cell<usize>>,
-    update: UseForceUpdateHandle,
+    wakers: Rc<RefCell<Vec<Waker>>>,
+    update: Option<UseForceUpdateHandle>,
+}
+
+/// A handle that isn't attached to any component, e.g. for states that were deserialized.
+/// Attach the state to a binding with [`Binding::restore()`] to have its changes rerender the component.
+impl Default for UseGenerationHandle {
+    fn default() -> Self {
+        Self {
+            generation: Rc::new(Cell::new(0)),
+            wakers: Rc::new(RefCell::new(Vec::new())),
+            update: None,
+        }
+    }
 }
 
 impl PartialEq for UseGenerationHandle {
@@ -95,13 +262,31 @@ impl UseGenerationHandle {
     pub fn increase(&self) -> usize {
         let next_gen = self.generation.get().wrapping_add(1);
         self.generation.set(next_gen);
-        self.update.force_update();
+        if let Some(update) = &self.update {
+            update.force_update();
+        }
+        self.wakers.take().into_iter().for_each(Waker::wake);
         next_gen
     }
 
     pub fn generation(&self) -> usize {
         self.generation.get()
     }
+
+    /// Resolves once the generation increases
+    pub fn changed(&self) -> impl Future<Output = ()> {
+        let handle = self.clone();
+        let generation = self.generation();
+
+        poll_fn(move |cx| {
+            if handle.generation() != generation {
+                Poll::Ready(())
+            } else {
+                handle.wakers.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
 }
 
 #[cfg_attr(not(test), hook)]
@@ -110,14 +295,15 @@ pub fn use_generation() -> UseGenerationHandle {
     {
         UseGenerationHandle {
             generation: use_memo(|_| Cell::new(0), ()),
-            update: use_force_update(),
+            wakers: use_memo(|_| RefCell::new(Vec::new()), ()),
+            update: Some(use_force_update()),
         }
     }
     #[cfg(test)]
     {
         UseGenerationHandle {
-            generation: Rc::new(Cell::new(0)),
-            update: UseForceUpdateHandle,
+            update: Some(UseForceUpdateHandle),
+            ..Default::default()
         }
     }
 }
@@ -163,6 +349,48 @@ where
     }
 }
 
+#[cfg(test)]
+pub struct UseReducerHandle<T>(Rc<RefCell<Rc<T>>>)
+where
+    T: Reducible;
+
+#[cfg(test)]
+impl<T> UseReducerHandle<T>
+where
+    T: Reducible,
+{
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(RefCell::new(Rc::new(value))))
+    }
+
+    pub fn dispatch(&self, action: T::Action) {
+        let state = self.0.borrow().clone();
+        *self.0.borrow_mut() = state.reduce(action);
+    }
+}
+
+#[cfg(test)]
+impl<T> Clone for UseReducerHandle<T>
+where
+    T: Reducible,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+impl<T> std::ops::Deref for UseReducerHandle<T>
+where
+    T: Reducible,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(*self.0).as_ptr() }
+    }
+}
+
 #[cfg(test)]
 pub fn use_named_binding_with_deps<T, D>(
     name: impl Into<AttrValue>,
@@ -175,11 +403,11 @@ where
 {
     let model = init_fn(&deps);
     let generation = use_generation();
-    let state = crate::model::State::create(&model, true, generation);
+    let state = crate::model::State::create(&model, true, generation.clone());
 
     Binding::new(
         std::rc::Rc::new(std::cell::RefCell::new((model, state))),
         name.into(),
-        0,
+        generation,
     )
 }
@@ -4,19 +4,35 @@ pub struct ReadmeDoctests;
 
 pub mod binding;
 pub mod components;
+pub mod diff;
+pub mod encoding;
 pub mod field;
+pub mod format;
+pub mod history;
 pub mod hooks;
 pub mod model;
 pub mod modifier;
+#[cfg(feature = "serde")]
+pub mod persist;
 pub mod prelude;
+pub mod query;
 pub mod state_model;
+pub mod submit;
+pub mod validation;
 
 #[doc(inline)]
 pub use binding::Binding;
 #[doc(inline)]
 pub use components::*;
+#[cfg(feature = "derive")]
 #[doc(inline)]
 pub use yfb_derive::Model;
 
 #[doc(inline)]
 pub use crate::model::Model;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+#[doc(hidden)]
+pub use yew;
@@ -1,6 +1,7 @@
 use std::{
     convert::Infallible,
     fmt::Display,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
     str::FromStr,
 };
@@ -8,9 +9,12 @@ use std::{
 use yew::AttrValue;
 
 use crate::{
-    field::{Field, FieldModifier},
+    field::{parse_error, Field, FieldModifier},
+    format::MessageFormatter,
     hooks::UseGenerationHandle,
     modifier::{Modifier, OptionModifier, VecModifier},
+    state_model::StateModelRc,
+    validation::{FieldError, ValidationErrors, ValidationErrorsKind},
 };
 
 pub trait Model: ModelState {
@@ -19,6 +23,34 @@ pub trait Model: ModelState {
 
 pub trait ModelMarker {}
 
+/// Implemented by a [`Model`] for itself and for every model it embeds through `#[yfb(flatten)]`, also through other flattened models,
+/// making the generated binding methods of `M` available on bindings of `Self`.
+/// `I` tells where `M` is embedded, it is inferred as long as `M` is embedded only once.
+pub trait Flatten<M, I>: ModelState
+where
+    M: ModelState,
+{
+    fn flatten(state_model: StateModelRc<Self>) -> StateModelRc<M>;
+}
+
+/// Index of a model in [`Flatten`] for the model itself
+pub struct Here;
+
+/// Index of a model in [`Flatten`] embedded through the flattened field mapped by `F`, at index `I` within that field's model
+pub struct Through<F, I>(PhantomData<(F, I)>);
+
+/// A [`Model`] with multiple variants, implemented by deriving [`Model`] on an enum
+pub trait EnumModel: ModelState {
+    /// A fieldless enum naming each variant of the model
+    type Variant: Copy + PartialEq + std::fmt::Debug + 'static;
+
+    /// Returns the currently active variant
+    fn variant(&self) -> Self::Variant;
+    /// Creates the model in `variant` with all its fields set to their default value.
+    /// Skipped fields are no exception: a value in a variant other than the active one only exists after switching to it, so they need `Default` too
+    fn from_variant(variant: Self::Variant) -> Self;
+}
+
 pub trait Value: ModelState<State = Field, Modifier = FieldModifier<Self>> {
     type Err;
 
@@ -31,27 +63,326 @@ pub trait ValueMarker {}
 pub trait ModelState: PartialEq + Sized + 'static {
     type State: State<Self> + PartialEq;
     type Modifier: Modifier<Self>;
+
+    /// Calls `visit` with the path and [`Value::to_value()`] of every field of the model, starting from `path`.
+    /// Paths follow binding names, e.g. `filter.tags[0]`. Enums also yield the name of their variant at their own path.
+    ///
+    /// Implemented for all derived models and values. The default visits nothing, which leaves models implementing
+    /// this trait by hand out of query strings and form data.
+    fn visit_values(&self, _path: &str, _visit: &mut dyn FnMut(&str, AttrValue)) {}
+
+    /// Sets the field at `path`, relative to the model like `.tags[0]`, to `value` parsed with [`Value::from_value()`].
+    /// Lists grow by one item at a time, when the item can be created from the path alone.
+    ///
+    /// The default reports [`AssignError::UnknownPath`] for every path.
+    fn assign_value(&mut self, _path: &str, _value: &AttrValue) -> Result<(), AssignError> {
+        Err(AssignError::UnknownPath)
+    }
+
+    /// Creates a model holding just the field at `path`, e.g. for a new item of a list.
+    /// Derived structs can only be created this way with `#[yfb(default)]`, otherwise they report [`AssignError::UnknownPath`].
+    ///
+    /// The default reports [`AssignError::UnknownPath`] for every path.
+    fn from_path(_path: &str, _value: &AttrValue) -> Result<Self, AssignError> {
+        Err(AssignError::UnknownPath)
+    }
+}
+
+/// Error assigning a value to a model by its path, see [`ModelState::assign_value()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignError {
+    /// The model has no field at the path, or the item of a list can't be created
+    UnknownPath,
+    /// The value could not be parsed into the field's type, holding the error with code [`PARSE_CODE`](crate::field::PARSE_CODE)
+    InvalidValue(FieldError),
+}
+
+impl Display for AssignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssignError::UnknownPath => write!(f, "unknown path"),
+            AssignError::InvalidValue(_) => write!(f, "invalid value"),
+        }
+    }
+}
+
+impl std::error::Error for AssignError {}
+
+#[doc(hidden)]
+/// Splits the leading `.name` off `path`, returning the name and the rest of the path
+pub fn split_field(path: &str) -> Option<(&str, &str)> {
+    let path = path.strip_prefix('.')?;
+    let end = path.find(['.', '[']).unwrap_or(path.len());
+    Some(path.split_at(end))
 }
 
-pub trait State<T>: Dirty + std::fmt::Debug
+#[doc(hidden)]
+/// Splits the leading `[index]` off `path`, returning the index and the rest of the path
+pub fn split_index(path: &str) -> Option<(usize, &str)> {
+    let (index, rest) = path.strip_prefix('[')?.split_once(']')?;
+    Some((index.parse().ok()?, rest))
+}
+
+#[doc(hidden)]
+/// Builds the `#[yfb(skip)]` fields of a variant that [`EnumModel::from_variant()`] switches to, as they have no state to build them from
+#[diagnostic::on_unimplemented(
+    message = "skipped field `{Self}` of an enum variant needs `Default`",
+    note = "switching to a variant creates its fields with their default value, including the skipped ones"
+)]
+pub trait SkippedDefault {
+    fn skipped_default() -> Self;
+}
+
+impl<T> SkippedDefault for T
+where
+    T: Default,
+{
+    fn skipped_default() -> Self {
+        T::default()
+    }
+}
+
+/// Validation backend of a [`Model`], called by the derived modifiers whenever the model changed.
+///
+/// With the `validator` feature it is implemented for every type deriving `validator::Validate`.
+/// With the `garde` feature, models deriving `garde::Validate` implement it by adding `#[yfb(garde)]`,
+/// their reports are converted into [`ValidationErrors`] keyed by the paths of the fields.
+/// Models without any validation derive it with `#[yfb(no_validate)]`.
+/// Other backends are used by implementing it by hand, converting their errors into [`ValidationErrors`]
+/// keyed by the identifiers of the fields.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no validation backend",
+    note = "derive `validator::Validate`, add `#[yfb(no_validate)]` or `#[yfb(garde)]`, or implement `Validator` for it"
+)]
+pub trait Validator {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+#[cfg(feature = "validator")]
+impl<T> Validator for T
+where
+    T: validator::Validate,
+{
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validator::Validate::validate(self).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "garde")]
+#[doc(hidden)]
+/// Validates `model` through `garde`, used by the `Validator` implementation of models with `#[yfb(garde)]`
+pub fn validate_garde<T>(model: &T) -> Result<(), ValidationErrors>
+where
+    T: garde::Validate,
+    T::Context: Default,
+{
+    garde::Validate::validate(model).map_err(Into::into)
+}
+
+pub trait State<T>: Dirty + Touched + Validating + SetErrors + Visit + std::fmt::Debug
 where
     T: ModelState,
 {
     fn create(model: &T, dirty: bool, generation: UseGenerationHandle) -> Self;
     fn update(&mut self, model: &T, replace: bool);
     fn generation(&self) -> usize;
+    /// Attaches the state and all nested states to `generation`, e.g. after the state was deserialized
+    fn attach(&mut self, generation: UseGenerationHandle);
+    /// Restores the parts of `model` tracked by the state to their initial values, other parts like skipped fields are kept.
+    /// Returns `false` when the state has no initial value, e.g. for items added to a list after the binding was created.
+    /// Lists also return `false` when a removed item can't be rebuilt, leaving `model` as is rather than restoring part of it.
+    fn restore_initial(&self, model: &mut T) -> bool;
+    /// Builds the model from the initial values of the state.
+    /// Returns `None` when the state has no initial value, or when the model has parts that aren't tracked, like skipped fields.
+    fn initial_model(&self) -> Option<T>;
+}
+
+/// Walks a state tree with a [`Visitor`], implemented by [`Field`], [`VecState`] and the derived states
+pub trait Visit {
+    /// Visits the state at `path`, the binding name of its model like `order.lines[0]`
+    fn visit(&self, path: &str, visitor: &mut dyn Visitor);
+}
+
+/// Receives the parts of a state tree in the order of the model, see [`Visit`]
+pub trait Visitor {
+    /// Called for every field of the model, including the fields of lists and of the active variant of enums
+    fn field(&mut self, path: &str, field: &Field);
+
+    /// Called for every list before its items, with its initial and current length
+    fn list(&mut self, _path: &str, _initial_length: usize, _length: usize) {}
+
+    /// Called for every enum before the fields of its active variant, with the names of its initial and active variant
+    fn variant(&mut self, _path: &str, _initial: Option<&str>, _current: &str) {}
+
+    /// Called for every derived model before its fields, with the messages that apply to the model as a whole
+    fn messages(&mut self, _path: &str, _messages: &Messages) {}
+
+    /// Called after the items of a list for the fields of the items that were removed from it, holding their initial values
+    fn removed(&mut self, _path: &str, _field: &Field) {}
+}
+
+/// Visits every field with its path, e.g. `binding.visit(&mut |path: &str, field: &Field| ..)`
+impl<F> Visitor for F
+where
+    F: FnMut(&str, &Field),
+{
+    fn field(&mut self, path: &str, field: &Field) {
+        self(path, field)
+    }
+}
+
+/// Passes the fields of removed items to [`Visitor::removed()`]
+struct Removed<'a>(&'a mut dyn Visitor);
+
+impl Visitor for Removed<'_> {
+    fn field(&mut self, path: &str, field: &Field) {
+        self.0.removed(path, field);
+    }
 }
 
 pub trait Dirty {
     fn dirty(&self) -> bool;
 }
 
+/// Tracks whether the user interacted with a field, for states this applies to any of the nested fields
+pub trait Touched {
+    /// Returns whether the field has lost focus at least once
+    fn touched(&self) -> bool;
+    /// Returns whether the field currently has focus
+    fn focused(&self) -> bool;
+    /// Marks the field and all nested fields as touched
+    fn touch(&mut self);
+}
+
+/// Shows the errors reported for a field or nested model by the validation of a parent model
+pub trait SetErrors {
+    /// Updates the messages of the field, or of the nested fields for states of models and lists.
+    /// Messages are only shown for dirty fields, unless `all` is set. Messages that are already shown are kept until the field becomes valid.
+    fn set_errors(&mut self, errors: Option<&ValidationErrorsKind>, all: bool);
+    /// Clears the messages and errors of the field, or of the nested fields, including errors parsing their input
+    fn clear_errors(&mut self);
+}
+
+/// Implemented by the states of derived models, holding the messages of errors that apply to the model as a whole,
+/// e.g. from `#[validate(schema(..))]` functions
+pub trait ModelMessages {
+    fn messages(&self) -> &Messages;
+    fn messages_mut(&mut self) -> &mut Messages;
+}
+
+/// Messages of a model's errors that are not tied to any of its fields, see [`ModelMessages`]
+#[derive(PartialEq)]
+pub struct Messages {
+    messages: Vec<FieldError>,
+    generation: usize,
+    generation_handle: UseGenerationHandle,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Messages {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(&self.messages)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Messages {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self {
+            messages: Vec::deserialize(deserializer)?,
+            ..Self::new(UseGenerationHandle::default())
+        })
+    }
+}
+
+impl std::fmt::Debug for Messages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.messages).finish()
+    }
+}
+
+impl Messages {
+    pub fn new(generation: UseGenerationHandle) -> Self {
+        Self {
+            messages: Vec::new(),
+            generation: generation.generation(),
+            generation_handle: generation,
+        }
+    }
+
+    /// Returns the errors of the current messages, empty when the model passed validation
+    pub fn get(&self) -> &[FieldError] {
+        &self.messages
+    }
+
+    /// Returns the current messages, formatted by `formatter`
+    pub fn format(&self, formatter: &dyn MessageFormatter) -> Vec<AttrValue> {
+        self.messages
+            .iter()
+            .map(|error| formatter.format(error))
+            .collect()
+    }
+
+    pub fn set(&mut self, messages: Vec<FieldError>) {
+        if self.messages != messages {
+            self.messages = messages;
+            self.generation = self.generation_handle.increase();
+        }
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn attach(&mut self, generation: UseGenerationHandle) {
+        self.generation = generation.generation();
+        self.generation_handle = generation;
+    }
+}
+
+/// Tracks the asynchronous validators of a field, for states this applies to any of the nested fields
+pub trait Validating {
+    /// Returns whether an asynchronous validator is still checking the current value
+    fn validating(&self) -> bool;
+}
+
 impl<T> ModelState for Vec<T>
 where
     T: ModelState,
 {
     type Modifier = VecModifier<T>;
     type State = VecState<T>;
+
+    fn visit_values(&self, path: &str, visit: &mut dyn FnMut(&str, AttrValue)) {
+        for (index, item) in self.iter().enumerate() {
+            item.visit_values(&format!("{path}[{index}]"), visit);
+        }
+    }
+
+    fn assign_value(&mut self, path: &str, value: &AttrValue) -> Result<(), AssignError> {
+        let (index, rest) = split_index(path).ok_or(AssignError::UnknownPath)?;
+        let length = self.len();
+        match self.get_mut(index) {
+            Some(item) => item.assign_value(rest, value),
+            None if index == length => {
+                self.push(T::from_path(rest, value)?);
+                Ok(())
+            }
+            None => Err(AssignError::UnknownPath),
+        }
+    }
+
+    fn from_path(path: &str, value: &AttrValue) -> Result<Self, AssignError> {
+        let mut model = Vec::new();
+        model.assign_value(path, value)?;
+        Ok(model)
+    }
 }
 
 impl<T> Model for Vec<T>
@@ -62,6 +393,14 @@ where
 }
 
 #[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "T::State: serde::Serialize",
+        deserialize = "T::State: serde::Deserialize<'de>"
+    ))
+)]
 pub struct VecState<T>
 where
     T: ModelState,
@@ -69,6 +408,7 @@ where
     // Keeps track of the initial length, states after this length may be purged. as their initial state is not needed.
     initial_length: usize,
     valid_length: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
     generation: UseGenerationHandle,
     pub(crate) current: Vec<T::State>,
 }
@@ -107,6 +447,9 @@ where
         if self.valid_length != model.len() {
             self.generation.increase();
         }
+        if replace {
+            self.initial_length = model.len();
+        }
         self.current.extend(
             model
                 .iter()
@@ -132,6 +475,56 @@ where
             .max()
             .unwrap_or_default()
     }
+
+    fn attach(&mut self, generation: UseGenerationHandle) {
+        for state in &mut self.current {
+            state.attach(generation.clone());
+        }
+        self.generation = generation;
+    }
+
+    fn restore_initial(&self, model: &mut Vec<T>) -> bool {
+        // The states of removed items are kept up to the initial length, so these items can be rebuilt
+        let kept = model.len().min(self.initial_length);
+        let Some(removed) = self.current[kept..self.initial_length]
+            .iter()
+            .map(|state| state.initial_model())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        model.truncate(self.initial_length);
+        for (state, item) in self.current.iter().zip(model.iter_mut()) {
+            state.restore_initial(item);
+        }
+        model.extend(removed);
+        true
+    }
+
+    fn initial_model(&self) -> Option<Vec<T>> {
+        self.current[..self.initial_length]
+            .iter()
+            .map(|state| state.initial_model())
+            .collect()
+    }
+}
+
+impl<T> Visit for VecState<T>
+where
+    T: ModelState,
+{
+    fn visit(&self, path: &str, visitor: &mut dyn Visitor) {
+        visitor.list(path, self.initial_length, self.valid_length);
+        for (index, state) in self.current.iter().enumerate() {
+            let path = format!("{path}[{index}]");
+            match index < self.valid_length {
+                true => state.visit(&path, visitor),
+                false if index < self.initial_length => state.visit(&path, &mut Removed(visitor)),
+                false => break,
+            }
+        }
+    }
 }
 
 impl<T> Dirty for VecState<T>
@@ -149,12 +542,95 @@ where
     }
 }
 
+impl<T> Touched for VecState<T>
+where
+    T: ModelState,
+{
+    fn touched(&self) -> bool {
+        self.current
+            .iter()
+            .take(self.valid_length)
+            .any(Touched::touched)
+    }
+
+    fn focused(&self) -> bool {
+        self.current
+            .iter()
+            .take(self.valid_length)
+            .any(Touched::focused)
+    }
+
+    fn touch(&mut self) {
+        self.current
+            .iter_mut()
+            .take(self.valid_length)
+            .for_each(Touched::touch);
+    }
+}
+
+impl<T> SetErrors for VecState<T>
+where
+    T: ModelState,
+{
+    fn set_errors(&mut self, errors: Option<&ValidationErrorsKind>, all: bool) {
+        let errors = match errors {
+            Some(ValidationErrorsKind::List(errors)) => Some(errors),
+            _ => None,
+        };
+
+        for (index, state) in self.current.iter_mut().take(self.valid_length).enumerate() {
+            let errors = errors
+                .and_then(|errors| errors.get(&index))
+                .map(|errors| ValidationErrorsKind::Struct(Box::new(errors.clone())));
+            state.set_errors(errors.as_ref(), all);
+        }
+    }
+
+    fn clear_errors(&mut self) {
+        self.current.iter_mut().for_each(SetErrors::clear_errors);
+    }
+}
+
+impl<T> Validating for VecState<T>
+where
+    T: ModelState,
+{
+    fn validating(&self) -> bool {
+        self.current
+            .iter()
+            .take(self.valid_length)
+            .any(Validating::validating)
+    }
+}
+
 impl<T> ModelState for Option<T>
 where
     T: ModelState + Default,
 {
     type Modifier = OptionModifier<T>;
     type State = T::State;
+
+    fn visit_values(&self, path: &str, visit: &mut dyn FnMut(&str, AttrValue)) {
+        if let Some(model) = self {
+            model.visit_values(path, visit);
+        }
+    }
+
+    fn assign_value(&mut self, path: &str, value: &AttrValue) -> Result<(), AssignError> {
+        match self {
+            Some(model) => model.assign_value(path, value),
+            None => {
+                *self = Self::from_path(path, value)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn from_path(path: &str, value: &AttrValue) -> Result<Self, AssignError> {
+        let mut model = T::default();
+        model.assign_value(path, value)?;
+        Ok(Some(model))
+    }
 }
 
 impl<T> Model for Option<T>
@@ -185,6 +661,27 @@ where
     fn generation(&self) -> usize {
         State::<T>::generation(self)
     }
+
+    fn attach(&mut self, generation: UseGenerationHandle) {
+        State::<T>::attach(self, generation)
+    }
+
+    fn restore_initial(&self, model: &mut Option<T>) -> bool {
+        match model.as_mut() {
+            // A state created for `None` has no initial value
+            Some(inner) => {
+                if !State::<T>::restore_initial(self, inner) {
+                    *model = None;
+                }
+            }
+            None => *model = State::<T>::initial_model(self),
+        }
+        true
+    }
+
+    fn initial_model(&self) -> Option<Option<T>> {
+        Some(State::<T>::initial_model(self))
+    }
 }
 
 impl<T> ModelState for T
@@ -193,6 +690,22 @@ where
 {
     type Modifier = FieldModifier<T>;
     type State = Field;
+
+    fn visit_values(&self, path: &str, visit: &mut dyn FnMut(&str, AttrValue)) {
+        visit(path, self.to_value());
+    }
+
+    fn assign_value(&mut self, path: &str, value: &AttrValue) -> Result<(), AssignError> {
+        *self = Self::from_path(path, value)?;
+        Ok(())
+    }
+
+    fn from_path(path: &str, value: &AttrValue) -> Result<Self, AssignError> {
+        if !path.is_empty() {
+            return Err(AssignError::UnknownPath);
+        }
+        T::from_value(value).map_err(|_| AssignError::InvalidValue(parse_error::<T>(value.clone())))
+    }
 }
 
 macro_rules! impl_value_marker {
@@ -233,6 +746,11 @@ impl Value for AttrValue {
 }
 
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Wrapped<T>(pub T);
 
 impl<T> Value for Wrapped<T>
@@ -3,8 +3,12 @@ use std::{cell::Ref, rc::Rc};
 use yew::AttrValue;
 
 use crate::{
-    model::{Dirty, ModelState, State, Value},
-    state_model::{MappedStateModel, MappedVecStateModel, Mapping, StateModelRc},
+    model::{Dirty, EnumModel, ModelState, SetErrors, State, Value},
+    state_model::{
+        MappedStateModel, MappedVariantStateModel, MappedVecStateModel, Mapping, StateModelRc,
+        VariantMapping,
+    },
+    validation::ValidationErrors,
 };
 
 pub type OptionModifier<T> = BaseModifier<Option<T>>;
@@ -24,20 +28,46 @@ where
         Modifier::create(state_model)
     }
 
-    fn model(&self) -> Ref<T> {
+    fn map_variant<M>(&self, mapping: M) -> <M::To as ModelState>::Modifier
+    where
+        M: VariantMapping<From = T>,
+    {
+        let state_model = Rc::new(MappedVariantStateModel::new(
+            self.state_model().clone(),
+            mapping,
+        ));
+        Modifier::create(state_model)
+    }
+
+    fn model(&self) -> Ref<'_, T> {
         self.state_model().model()
     }
 
-    fn state(&self) -> Ref<T::State> {
+    fn state(&self) -> Ref<'_, T::State> {
         self.state_model().state()
     }
 
     fn set_message(&self, _message: Option<AttrValue>) {}
 
+    /// Validates the model and updates the messages of its fields.
+    /// Messages are only shown for dirty fields, unless `all` is set.
+    fn validate(&self, _all: bool) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+
     fn dirty(&self) -> bool {
         self.state().dirty()
     }
 
+    /// Restores the model to its initial value and clears the messages and errors of its fields.
+    /// Parts of the model that aren't bound, like skipped fields, are kept.
+    fn reset(&self) {
+        let (mut model, mut state) = self.state_model().as_mut();
+        State::<T>::restore_initial(&*state, &mut *model);
+        State::<T>::update(&mut *state, &*model, false);
+        state.clear_errors();
+    }
+
     fn replace_model(&self, model: T) {
         let (mut model_mut, mut state) = self.state_model().as_mut();
         *model_mut = model;
@@ -45,6 +75,31 @@ where
     }
 }
 
+/// Implemented by modifiers of an [`EnumModel`]
+pub trait VariantModifier<T>: Modifier<T>
+where
+    T: EnumModel,
+{
+    /// Switches the model to `variant`, the fields of the new variant are set to their default value.
+    /// Does nothing if `variant` is already active.
+    fn switch_variant(&self, variant: T::Variant) {
+        let (mut model, mut state) = self.state_model().as_mut();
+        if model.variant() != variant {
+            *model = T::from_variant(variant);
+            State::<T>::update(&mut *state, &*model, false);
+        }
+    }
+}
+
+/// Implemented by a model's modifier for the model itself and for every model it embeds through `#[yfb(flatten)]`,
+/// making the generated modifier methods of `M` available on `Self`. `I` is the index of `M` in [`Flatten`](crate::model::Flatten).
+pub trait FlattenModifier<M, I>
+where
+    M: ModelState,
+{
+    fn flatten(&self) -> StateModelRc<M>;
+}
+
 pub struct BaseModifier<T>(pub(crate) StateModelRc<T>)
 where
     T: ModelState;
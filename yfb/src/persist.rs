@@ -0,0 +1,120 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{binding::Binding, model::ModelState};
+
+/// Keeps drafts of models as text under a key, see [`use_persisted_binding()`](fn@crate::hooks::use_persisted_binding)
+pub trait DraftStorage {
+    /// Returns the draft stored under `key`
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores `value` under `key`, replacing any previous draft.
+    /// Drafts are kept on a best effort basis, failures like an exceeded quota are ignored.
+    fn set(&self, key: &str, value: &str);
+
+    /// Removes the draft stored under `key`, e.g. once the model was submitted
+    fn remove(&self, key: &str);
+}
+
+/// Keeps drafts in the browser's `localStorage`, surviving a restart of the browser.
+/// Nothing is kept when `localStorage` is unavailable, e.g. when the user disabled it or outside of a browser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocalStorage;
+
+/// Keeps drafts in the browser's `sessionStorage`, surviving a reload of the page but not the tab being closed.
+/// Drafts are scoped to the tab, a form opened in two tabs keeps a separate draft in each.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStorage;
+
+impl DraftStorage for LocalStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        local_storage()?.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+impl DraftStorage for SessionStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        session_storage()?.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+/// Returns `localStorage`, which is missing when it was disabled by the user
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Returns `sessionStorage`, which is missing when it was disabled by the user
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok().flatten()
+}
+
+/// Keeps drafts in a map in memory that all clones of the storage share.
+/// Drafts are lost with the page, the storage is meant for checking what a form persisted in native tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage(Rc<RefCell<HashMap<String, String>>>);
+
+impl DraftStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.0.borrow_mut().insert(key.to_owned(), value.to_owned());
+    }
+
+    fn remove(&self, key: &str) {
+        self.0.borrow_mut().remove(key);
+    }
+}
+
+impl PartialEq for MemoryStorage {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Loads the model and its state stored under `key`.
+/// Returns `None` when there is no draft, or when it doesn't match the model anymore, e.g. after a field was added.
+pub fn load_draft<T>(storage: &impl DraftStorage, key: &str) -> Option<(T, T::State)>
+where
+    T: ModelState + DeserializeOwned,
+    T::State: DeserializeOwned,
+{
+    serde_json::from_str(&storage.get(key)?).ok()
+}
+
+/// Stores the model of `binding` and its state under `key`
+pub fn save_draft<T>(storage: &impl DraftStorage, key: &str, binding: &Binding<T>)
+where
+    T: ModelState + Serialize,
+    T::State: Serialize,
+{
+    if let Ok(draft) = serde_json::to_string(&(&*binding.model(), &*binding.state())) {
+        storage.set(key, &draft);
+    }
+}
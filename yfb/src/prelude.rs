@@ -1,8 +1,19 @@
+#[cfg(feature = "derive")]
 pub use yfb_derive::Model;
 
 pub use crate::{
     binding::Binding,
+    format::{use_formatter, MessageFormatter},
+    history::Coalesce,
     hooks::*,
-    model::{Dirty, Model, State, Value, ValueMarker, Wrapped},
-    modifier::Modifier,
+    model::{
+        Dirty, EnumModel, Model, ModelMessages, State, Touched, Validating, Value, ValueMarker,
+        Wrapped,
+    },
+    modifier::{Modifier, VariantModifier},
+    submit::use_form_submit,
+    validation::TargetFields,
 };
+
+#[cfg(feature = "serde")]
+pub use crate::persist::DraftStorage;
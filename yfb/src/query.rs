@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+use web_sys::wasm_bindgen::JsValue;
+
+use crate::{
+    binding::Binding,
+    encoding::{assign_pairs, strip_name, to_pairs},
+    model::ModelState,
+};
+
+/// Access to the query string of the page's URL, see [`use_query_binding()`](fn@crate::hooks::use_query_binding)
+pub trait UrlQuery {
+    /// Returns the query string without the leading `?`
+    fn get(&self) -> String;
+
+    /// Replaces the query string, without adding an entry to the browser's history
+    fn replace(&self, query: &str);
+}
+
+/// The query string of the browser's location.
+/// Outside of a browser the query is empty and replacing it does nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocationQuery;
+
+impl UrlQuery for LocationQuery {
+    fn get(&self) -> String {
+        web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .map(|search| search.trim_start_matches('?').to_owned())
+            .unwrap_or_default()
+    }
+
+    fn replace(&self, query: &str) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let location = window.location();
+        let (Ok(path), Ok(hash), Ok(history)) =
+            (location.pathname(), location.hash(), window.history())
+        else {
+            return;
+        };
+
+        let url = match query {
+            "" => format!("{path}{hash}"),
+            query => format!("{path}?{query}{hash}"),
+        };
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+}
+
+/// A query string held in memory, e.g. to start a native test from a given URL with [`MemoryQuery::new()`].
+/// Clones refer to the same query, so a clone passed to a binding shows what it wrote.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryQuery(Rc<RefCell<String>>);
+
+impl MemoryQuery {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self(Rc::new(RefCell::new(query.into())))
+    }
+}
+
+impl UrlQuery for MemoryQuery {
+    fn get(&self) -> String {
+        self.0.borrow().clone()
+    }
+
+    fn replace(&self, query: &str) {
+        *self.0.borrow_mut() = query.to_owned();
+    }
+}
+
+impl PartialEq for MemoryQuery {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Assigns the pairs of the query whose key starts with `name` to `model`, like `name.tags[0]=a`.
+/// Pairs that don't fit the model or can't be parsed are ignored, keeping the value of `model`.
+pub fn load_query<T>(query: &impl UrlQuery, name: &str, model: &mut T)
+where
+    T: ModelState,
+{
+    let query = query.get();
+    let pairs =
+        form_urlencoded::parse(query.as_bytes()).map(|(key, value)| (key, value.into_owned()));
+    assign_pairs(model, name, pairs);
+}
+
+/// Writes the values of the model of `binding` to the query, keyed by their binding names.
+/// Pairs with keys outside of the binding's name are kept.
+pub fn save_query<T>(query: &impl UrlQuery, binding: &Binding<T>)
+where
+    T: ModelState,
+{
+    let name = binding.name();
+    let current = query.get();
+
+    let updated = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(
+            form_urlencoded::parse(current.as_bytes())
+                .filter(|(key, _)| strip_name(key, name).is_none()),
+        )
+        .extend_pairs(to_pairs(&*binding.model(), name))
+        .finish();
+    if updated != current {
+        query.replace(&updated);
+    }
+}
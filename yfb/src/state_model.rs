@@ -3,20 +3,26 @@ use std::{
     rc::Rc,
 };
 
-use crate::model::ModelState;
+use crate::{
+    history::Change,
+    model::{EnumModel, ModelState, State},
+};
 
 pub type StateModelRc<T> = Rc<dyn StateModel<Model = T>>;
 
 pub trait StateModel {
     type Model: ModelState;
-    fn model(&self) -> Ref<Self::Model>;
-    fn state(&self) -> Ref<<Self::Model as ModelState>::State>;
+    fn model(&self) -> Ref<'_, Self::Model>;
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State>;
     fn as_mut(
         &self,
     ) -> (
-        RefMut<Self::Model>,
-        RefMut<<Self::Model as ModelState>::State>,
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
     );
+
+    /// Reports how the model was just changed to the history of the root model, if it has one
+    fn record(&self, _change: Change) {}
 }
 
 impl<T> StateModel for RefCell<(T, T::State)>
@@ -25,19 +31,19 @@ where
 {
     type Model = T;
 
-    fn model(&self) -> Ref<Self::Model> {
+    fn model(&self) -> Ref<'_, Self::Model> {
         Ref::map(self.borrow(), |(model, _)| model)
     }
 
-    fn state(&self) -> Ref<<Self::Model as ModelState>::State> {
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State> {
         Ref::map(self.borrow(), |(_, state)| state)
     }
 
     fn as_mut(
         &self,
     ) -> (
-        RefMut<Self::Model>,
-        RefMut<<Self::Model as ModelState>::State>,
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
     ) {
         RefMut::map_split(self.borrow_mut(), |(model, state)| (model, state))
     }
@@ -69,22 +75,26 @@ where
 {
     type Model = T;
 
-    fn model(&self) -> Ref<T> {
+    fn model(&self) -> Ref<'_, T> {
         Ref::filter_map(self.parent.model(), Option::as_ref)
             .unwrap_or_else(|_| self.shadow.borrow())
     }
 
-    fn state(&self) -> Ref<T::State> {
+    fn state(&self) -> Ref<'_, T::State> {
         self.parent.state()
     }
 
-    fn as_mut(&self) -> (RefMut<T>, RefMut<T::State>) {
+    fn as_mut(&self) -> (RefMut<'_, T>, RefMut<'_, T::State>) {
         let (model, state) = self.parent.as_mut();
 
         let model = RefMut::map(model, |m| m.get_or_insert_with(Default::default));
 
         (model, state)
     }
+
+    fn record(&self, change: Change) {
+        self.parent.record(change)
+    }
 }
 
 pub struct MappedVecStateModel<T>
@@ -110,12 +120,12 @@ where
 {
     type Model = T;
 
-    fn model(&self) -> Ref<Self::Model> {
+    fn model(&self) -> Ref<'_, Self::Model> {
         debug_assert!(self.parent.model().len() <= self.parent.state().current.len());
         Ref::map(self.parent.model(), |v| &v[self.index])
     }
 
-    fn state(&self) -> Ref<<Self::Model as ModelState>::State> {
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State> {
         debug_assert!(self.parent.model().len() <= self.parent.state().current.len());
         debug_assert!(self.index <= self.parent.model().len());
         Ref::map(self.parent.state(), |v| &v.current[self.index])
@@ -124,8 +134,8 @@ where
     fn as_mut(
         &self,
     ) -> (
-        RefMut<Self::Model>,
-        RefMut<<Self::Model as ModelState>::State>,
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
     ) {
         let (model, state) = self.parent.as_mut();
         debug_assert!(model.len() <= state.current.len());
@@ -134,6 +144,10 @@ where
             RefMut::map(state, |v| &mut v.current[self.index]),
         )
     }
+
+    fn record(&self, change: Change) {
+        self.parent.record(change)
+    }
 }
 
 pub trait Mapping: 'static {
@@ -176,19 +190,19 @@ where
 {
     type Model = M::To;
 
-    fn model(&self) -> Ref<Self::Model> {
+    fn model(&self) -> Ref<'_, Self::Model> {
         Ref::map(self.parent.model(), |m| self.mapping.map_model(m))
     }
 
-    fn state(&self) -> Ref<<Self::Model as ModelState>::State> {
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State> {
         Ref::map(self.parent.state(), |s| self.mapping.map_state(s))
     }
 
     fn as_mut(
         &self,
     ) -> (
-        RefMut<Self::Model>,
-        RefMut<<Self::Model as ModelState>::State>,
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
     ) {
         let (model, state) = self.parent.as_mut();
         (
@@ -196,4 +210,95 @@ where
             RefMut::map(state, |s| self.mapping.map_state_mut(s)),
         )
     }
+
+    fn record(&self, change: Change) {
+        self.parent.record(change)
+    }
+}
+
+pub trait VariantMapping: 'static {
+    const NAME: &'static str;
+    type From: EnumModel;
+    type To: ModelState + Default;
+
+    /// The variant of [`Self::From`] the mapped field belongs to
+    fn variant(&self) -> <Self::From as EnumModel>::Variant;
+    fn map_model<'a>(&self, model: &'a Self::From) -> Option<&'a Self::To>;
+    fn map_model_mut<'a>(&self, model: &'a mut Self::From) -> Option<&'a mut Self::To>;
+    fn map_state<'a>(
+        &self,
+        state: &'a <Self::From as ModelState>::State,
+    ) -> &'a <Self::To as ModelState>::State;
+    fn map_state_mut<'a>(
+        &self,
+        state: &'a mut <Self::From as ModelState>::State,
+    ) -> &'a mut <Self::To as ModelState>::State;
+}
+
+/// Maps to a field of an enum variant.
+/// Reading while another variant is active yields a default value, modifying switches to the mapped variant.
+pub struct MappedVariantStateModel<M>
+where
+    M: VariantMapping,
+{
+    parent: Rc<dyn StateModel<Model = M::From>>,
+    mapping: M,
+    shadow: RefCell<M::To>,
+}
+
+impl<M> MappedVariantStateModel<M>
+where
+    M: VariantMapping,
+{
+    pub fn new(parent: Rc<dyn StateModel<Model = M::From>>, mapping: M) -> Self {
+        Self {
+            parent,
+            mapping,
+            shadow: Default::default(),
+        }
+    }
+}
+
+impl<M> StateModel for MappedVariantStateModel<M>
+where
+    M: VariantMapping,
+{
+    type Model = M::To;
+
+    fn model(&self) -> Ref<'_, Self::Model> {
+        Ref::filter_map(self.parent.model(), |m| self.mapping.map_model(m))
+            .unwrap_or_else(|_| self.shadow.borrow())
+    }
+
+    fn state(&self) -> Ref<'_, <Self::Model as ModelState>::State> {
+        Ref::map(self.parent.state(), |s| self.mapping.map_state(s))
+    }
+
+    fn as_mut(
+        &self,
+    ) -> (
+        RefMut<'_, Self::Model>,
+        RefMut<'_, <Self::Model as ModelState>::State>,
+    ) {
+        let (mut model, mut state) = self.parent.as_mut();
+        let variant = self.mapping.variant();
+
+        if model.variant() != variant {
+            *model = EnumModel::from_variant(variant);
+            State::update(&mut *state, &*model, false);
+        }
+
+        (
+            RefMut::map(model, |m| {
+                self.mapping
+                    .map_model_mut(m)
+                    .expect("mapped variant should be active")
+            }),
+            RefMut::map(state, |s| self.mapping.map_state_mut(s)),
+        )
+    }
+
+    fn record(&self, change: Change) {
+        self.parent.record(change)
+    }
 }
@@ -0,0 +1,338 @@
+use std::{cell::Cell, future::Future, pin::Pin, rc::Rc};
+
+use yew::{platform::spawn_local, prelude::*};
+
+#[cfg(test)]
+use crate::hooks::UseReducerHandle;
+use crate::{
+    binding::Binding,
+    model::{ModelState, Validating},
+    validation::ValidationErrors,
+};
+
+type SubmitFn<T> = Rc<dyn Fn(T) -> Pin<Box<dyn Future<Output = ()>>>>;
+
+/// Tracks the submissions of a form, see [`use_form_submit()`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmitState {
+    validating: bool,
+    submitting: bool,
+    submitted: bool,
+    rejected: bool,
+    submit_count: usize,
+}
+
+impl SubmitState {
+    /// Returns whether a submission waits for asynchronous validators before it is either started or rejected
+    pub fn validating(&self) -> bool {
+        self.validating
+    }
+
+    /// Returns whether a submission passed validation and is still in progress
+    pub fn submitting(&self) -> bool {
+        self.submitting
+    }
+
+    /// Returns whether a submission passed validation and completed
+    pub fn submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns whether the last attempted submission failed validation, including after waiting for asynchronous validators
+    pub fn rejected(&self) -> bool {
+        self.rejected
+    }
+
+    /// Returns the number of attempted submissions, including the ones that failed validation
+    pub fn submit_count(&self) -> usize {
+        self.submit_count
+    }
+}
+
+#[doc(hidden)]
+pub enum SubmitAction {
+    Attempt,
+    Validate,
+    Start,
+    Reject,
+    Finish,
+}
+
+impl Reducible for SubmitState {
+    type Action = SubmitAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = *self;
+        match action {
+            SubmitAction::Attempt => {
+                state.submit_count += 1;
+                state.rejected = false;
+            }
+            SubmitAction::Validate => state.validating = true,
+            SubmitAction::Start => {
+                state.validating = false;
+                state.submitting = true;
+            }
+            SubmitAction::Reject => {
+                state.validating = false;
+                state.rejected = true;
+            }
+            SubmitAction::Finish => {
+                state.submitting = false;
+                state.submitted = true;
+            }
+        }
+        state.into()
+    }
+}
+
+/// Handle returned by [`use_form_submit()`]
+pub struct UseFormSubmitHandle<T>
+where
+    T: ModelState,
+{
+    binding: Binding<T>,
+    state: UseReducerHandle<SubmitState>,
+    // Unlike `state`, which only updates on the next render, this is set as soon as a submission is underway
+    busy: Rc<Cell<bool>>,
+    submit_fn: SubmitFn<T>,
+}
+
+impl<T> UseFormSubmitHandle<T>
+where
+    T: ModelState + Clone,
+{
+    /// Validates the whole model, showing the messages of all its fields.
+    /// When valid, the submit function is spawned with a copy of the model.
+    ///
+    /// While asynchronous validators are pending, the submission waits for them, see [`SubmitState::validating()`].
+    /// Once they completed it is either started or, when the model turns out invalid, rejected, see [`SubmitState::rejected()`].
+    ///
+    /// Calls made while a previous submission is still validating or in progress are ignored, they return `Ok(())` without being counted.
+    pub fn submit(&self) -> Result<(), ValidationErrors> {
+        if self.busy.get() {
+            return Ok(());
+        }
+        self.state.dispatch(SubmitAction::Attempt);
+
+        match self.binding.submit() {
+            Ok(model) => self.spawn(model),
+            Err(errors) if !self.binding.state().validating() => {
+                self.state.dispatch(SubmitAction::Reject);
+                return Err(errors);
+            }
+            Err(_) => {
+                self.busy.set(true);
+                self.state.dispatch(SubmitAction::Validate);
+
+                let handle = self.clone();
+                spawn_local(async move {
+                    match handle.binding.submit_async().await {
+                        Ok(model) => handle.spawn(model),
+                        Err(_) => {
+                            handle.busy.set(false);
+                            handle.state.dispatch(SubmitAction::Reject);
+                        }
+                    }
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn spawn(&self, model: T) {
+        self.busy.set(true);
+        self.state.dispatch(SubmitAction::Start);
+
+        let state = self.state.clone();
+        let busy = self.busy.clone();
+        let future = (self.submit_fn)(model);
+        spawn_local(async move {
+            future.await;
+            busy.set(false);
+            state.dispatch(SubmitAction::Finish);
+        });
+    }
+
+    /// Creates a callback for a form's `onsubmit`, preventing the browser from submitting the form itself
+    pub fn onsubmit(&self) -> Callback<SubmitEvent> {
+        let handle = self.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let _ = handle.submit();
+        })
+    }
+
+    /// Creates a callback that submits on any event, e.g. a button's `onclick`
+    pub fn callback<E>(&self) -> Callback<E> {
+        let handle = self.clone();
+        Callback::from(move |_| {
+            let _ = handle.submit();
+        })
+    }
+
+    /// Returns the current state of the submissions
+    pub fn state(&self) -> SubmitState {
+        *self.state
+    }
+
+    /// See [`SubmitState::validating()`]
+    pub fn validating(&self) -> bool {
+        self.state.validating
+    }
+
+    /// See [`SubmitState::submitting()`]
+    pub fn submitting(&self) -> bool {
+        self.state.submitting
+    }
+
+    /// See [`SubmitState::submitted()`]
+    pub fn submitted(&self) -> bool {
+        self.state.submitted
+    }
+
+    /// See [`SubmitState::rejected()`]
+    pub fn rejected(&self) -> bool {
+        self.state.rejected
+    }
+
+    /// See [`SubmitState::submit_count()`]
+    pub fn submit_count(&self) -> usize {
+        self.state.submit_count
+    }
+}
+
+impl<T> Clone for UseFormSubmitHandle<T>
+where
+    T: ModelState,
+{
+    fn clone(&self) -> Self {
+        Self {
+            binding: self.binding.clone(),
+            state: self.state.clone(),
+            busy: self.busy.clone(),
+            submit_fn: self.submit_fn.clone(),
+        }
+    }
+}
+
+/// Submits the model of `binding` through `submit_fn`.
+/// Submitting validates the whole model regardless of which fields are dirty, `submit_fn` is only called when it is valid.
+#[cfg_attr(not(test), hook)]
+pub fn use_form_submit<T, F, Fut>(binding: &Binding<T>, submit_fn: F) -> UseFormSubmitHandle<T>
+where
+    T: ModelState + Clone,
+    F: Fn(T) -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    #[cfg(not(test))]
+    let (state, busy) = (
+        use_reducer(SubmitState::default),
+        (*use_memo(|_| Rc::new(Cell::new(false)), ())).clone(),
+    );
+    #[cfg(test)]
+    let (state, busy) = (
+        UseReducerHandle::new(SubmitState::default()),
+        Rc::new(Cell::new(false)),
+    );
+
+    UseFormSubmitHandle {
+        binding: binding.clone(),
+        state,
+        busy,
+        submit_fn: Rc::new(move |model| Box::pin(submit_fn(model))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use validator::Validate;
+    use yfb_derive::Model;
+
+    use super::*;
+    use crate::prelude::*;
+
+    async fn available(name: String) -> Result<(), validator::ValidationError> {
+        tokio::task::yield_now().await;
+        match name.as_str() {
+            "taken" => Err(validator::ValidationError::new("taken")),
+            _ => Ok(()),
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+    #[yfb(path = "crate")]
+    struct Account {
+        #[validate(length(min = 3, message = "Too short"))]
+        #[yfb(async_validator = "available")]
+        name: String,
+    }
+
+    /// Lets the spawned validators and submissions run to completion
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[test]
+    fn test_form_submit() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, async {
+            let binding = use_binding(Account::default);
+            let submitted = Rc::new(RefCell::new(Vec::new()));
+            let submit = {
+                let submitted = submitted.clone();
+                use_form_submit(&binding, move |account: Account| {
+                    let submitted = submitted.clone();
+                    async move {
+                        tokio::task::yield_now().await;
+                        submitted.borrow_mut().push(account.name);
+                    }
+                })
+            };
+
+            // Invalid models are rejected right away
+            assert!(submit.submit().is_err());
+            assert!(submit.rejected());
+            assert_eq!(submit.submit_count(), 1);
+
+            // Pending asynchronous validators defer the outcome, which turns out invalid
+            binding.modifier().name().set("taken");
+            assert!(submit.submit().is_ok());
+            assert!(submit.validating());
+            assert!(!submit.rejected());
+            settle().await;
+            assert!(!submit.validating());
+            assert!(submit.rejected());
+            assert!(!submit.submitted());
+            assert!(submitted.borrow().is_empty());
+
+            // Calls while a submission is underway are ignored
+            binding.modifier().name().set("free");
+            assert!(submit.submit().is_ok());
+            assert!(submit.validating());
+            assert!(submit.submit().is_ok());
+            assert_eq!(submit.submit_count(), 3);
+            settle().await;
+            assert!(submit.submitted());
+            assert!(!submit.submitting());
+            assert!(!submit.rejected());
+            assert_eq!(*submitted.borrow(), ["free"]);
+
+            // Once checked, valid models are submitted right away
+            assert!(submit.submit().is_ok());
+            assert!(submit.submitting());
+            assert!(submit.submit().is_ok());
+            assert_eq!(submit.submit_count(), 4);
+            settle().await;
+            assert!(!submit.submitting());
+            assert_eq!(*submitted.borrow(), ["free", "free"]);
+        });
+    }
+}
@@ -0,0 +1,443 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+#[cfg(feature = "validator")]
+use validator::ValidationError;
+use yew::AttrValue;
+
+/// Key of the errors that apply to the model as a whole, like those of `validator`'s `#[validate(schema(..))]` functions
+pub const SCHEMA_KEY: &str = "__all__";
+
+/// Parameter of a schema error holding the comma separated fields it is targeted at, see [`TargetFields`]
+pub const TARGET_PARAM: &str = "fields";
+
+/// Code of the [`FieldError`]s converted from a `garde::Report`, whose errors only have a message
+#[cfg(feature = "garde")]
+pub const GARDE_CODE: &str = "garde";
+
+/// Errors of a model reported by its [`Validator`](crate::model::Validator), keyed by the identifiers of its fields.
+/// Errors that apply to the model as a whole are added under [`SCHEMA_KEY`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationErrors(BTreeMap<String, ValidationErrorsKind>);
+
+/// Errors of a single field, a nested model or the items of a list
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorsKind {
+    Struct(Box<ValidationErrors>),
+    List(BTreeMap<usize, ValidationErrors>),
+    Field(Vec<FieldError>),
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error of `field`, keeping its previous errors
+    pub fn add(&mut self, field: impl Into<String>, error: impl Into<FieldError>) {
+        let kind = self
+            .0
+            .entry(field.into())
+            .or_insert_with(|| ValidationErrorsKind::Field(Vec::new()));
+        if let ValidationErrorsKind::Field(errors) = kind {
+            errors.push(error.into());
+        }
+    }
+
+    /// Adds the errors of the nested model `field`, replacing any previous errors
+    pub fn add_nested(&mut self, field: impl Into<String>, errors: ValidationErrors) {
+        self.0
+            .insert(field.into(), ValidationErrorsKind::Struct(Box::new(errors)));
+    }
+
+    /// Adds the errors of the item at `index` of the list `field`, replacing any previous errors of the item
+    pub fn add_item(&mut self, field: impl Into<String>, index: usize, errors: ValidationErrors) {
+        let kind = self
+            .0
+            .entry(field.into())
+            .or_insert_with(|| ValidationErrorsKind::List(BTreeMap::new()));
+        if let ValidationErrorsKind::List(items) = kind {
+            items.insert(index, errors);
+        }
+    }
+
+    /// Returns the errors by the identifiers of the fields
+    pub fn errors(&self) -> &BTreeMap<String, ValidationErrorsKind> {
+        &self.0
+    }
+
+    /// Returns the errors of the fields that are not nested models or lists
+    pub fn field_errors(&self) -> BTreeMap<&str, &[FieldError]> {
+        self.0
+            .iter()
+            .filter_map(|(field, kind)| match kind {
+                ValidationErrorsKind::Field(errors) => Some((field.as_str(), errors.as_slice())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the errors of `field`
+    pub fn get(&self, field: &str) -> Option<&ValidationErrorsKind> {
+        self.0.get(field)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `Ok(())` when there are no errors, or else the errors
+    pub fn into_result(self) -> Result<(), Self> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+
+    /// Returns whether `result` has errors for `field`
+    pub fn has_error(result: &Result<(), Self>, field: &str) -> bool {
+        result
+            .as_ref()
+            .err()
+            .is_some_and(|errors| errors.0.contains_key(field))
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, (field, kind)) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            match kind {
+                ValidationErrorsKind::Struct(errors) => write!(f, "{field}: {errors}")?,
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        write!(f, "{field}[{index}]: {errors}")?;
+                    }
+                }
+                ValidationErrorsKind::Field(errors) => {
+                    let codes = errors.iter().map(|error| error.code().as_str());
+                    write!(f, "{field}: {}", codes.collect::<Vec<_>>().join(", "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for ValidationErrors {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let errors = errors.into_errors().into_iter();
+        Self(
+            errors
+                .map(|(field, kind)| (field.to_owned(), kind.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrorsKind> for ValidationErrorsKind {
+    fn from(kind: validator::ValidationErrorsKind) -> Self {
+        match kind {
+            validator::ValidationErrorsKind::Struct(errors) => {
+                Self::Struct(Box::new((*errors).into()))
+            }
+            validator::ValidationErrorsKind::List(items) => Self::List(
+                items
+                    .into_iter()
+                    .map(|(index, errors)| (index, (*errors).into()))
+                    .collect(),
+            ),
+            validator::ValidationErrorsKind::Field(errors) => {
+                Self::Field(errors.iter().map(FieldError::from).collect())
+            }
+        }
+    }
+}
+
+/// Errors on the model itself, with an empty path, are added under [`SCHEMA_KEY`].
+/// A path component without a key, like the value of an `Option`, doesn't nest its errors.
+#[cfg(feature = "garde")]
+impl From<garde::Report> for ValidationErrors {
+    fn from(report: garde::Report) -> Self {
+        let mut errors = Self::new();
+        for (path, error) in report.iter() {
+            // Components are iterated from the innermost one
+            let path = path
+                .__iter()
+                .rev()
+                .filter(|(kind, _)| *kind != garde::error::Kind::None)
+                .map(|(kind, component)| (kind, component.as_str()))
+                .collect::<Vec<_>>();
+            let error = FieldError::new(GARDE_CODE).with_message(error.message().to_owned());
+            errors.add_path(&path, error);
+        }
+        errors
+    }
+}
+
+#[cfg(feature = "garde")]
+impl ValidationErrors {
+    /// Adds `error` at the `garde` path of its field.
+    /// An index followed by further components is an item of a list, otherwise it's a field of a tuple struct,
+    /// as `garde` reports both by their index
+    fn add_path(&mut self, path: &[(garde::error::Kind, &str)], error: FieldError) {
+        use garde::error::Kind;
+
+        match path {
+            [] => self.add(SCHEMA_KEY, error),
+            [(_, field)] => self.add(*field, error),
+            [(_, field), (Kind::Index, index), rest @ ..] if !rest.is_empty() => {
+                let Ok(index) = index.parse() else {
+                    return;
+                };
+                let kind = self
+                    .0
+                    .entry(field.to_string())
+                    .or_insert_with(|| ValidationErrorsKind::List(BTreeMap::new()));
+                if let ValidationErrorsKind::List(items) = kind {
+                    items.entry(index).or_default().add_path(rest, error);
+                }
+            }
+            [(_, field), rest @ ..] => {
+                let kind = self
+                    .0
+                    .entry(field.to_string())
+                    .or_insert_with(|| ValidationErrorsKind::Struct(Box::default()));
+                if let ValidationErrorsKind::Struct(errors) = kind {
+                    errors.add_path(rest, error);
+                }
+            }
+        }
+    }
+}
+
+/// A validation error of a field with its code, message and params
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "FieldErrorData", from = "FieldErrorData")
+)]
+pub struct FieldError {
+    code: AttrValue,
+    message: Option<AttrValue>,
+    params: Vec<(AttrValue, AttrValue)>,
+}
+
+impl FieldError {
+    pub fn new(code: impl Into<AttrValue>) -> Self {
+        Self {
+            code: code.into(),
+            message: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Sets the message of the error
+    pub fn with_message(mut self, message: impl Into<AttrValue>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Adds the param `name`, replacing any previous value
+    pub fn with_param(mut self, name: impl Into<AttrValue>, value: impl Into<AttrValue>) -> Self {
+        let name = name.into();
+        self.params.retain(|(param, _)| *param != name);
+        self.params.push((name, value.into()));
+        self.params
+            .sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        self
+    }
+
+    /// Returns the code of the rule that failed, e.g. `length` or `range`
+    pub fn code(&self) -> &AttrValue {
+        &self.code
+    }
+
+    /// Returns the message the rule was declared with, if any
+    pub fn message(&self) -> Option<&AttrValue> {
+        self.message.as_ref()
+    }
+
+    /// Returns the params of the rule that failed sorted by name, e.g. `min` and `max` for `length`
+    pub fn params(&self) -> &[(AttrValue, AttrValue)] {
+        &self.params
+    }
+
+    /// Returns the param called `name`
+    pub fn param(&self, name: &str) -> Option<&AttrValue> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(feature = "validator")]
+impl From<&ValidationError> for FieldError {
+    fn from(error: &ValidationError) -> Self {
+        fn to_string(value: &serde_json::Value) -> String {
+            // Bounds of `range` are floats, whole ones are shown without a fraction
+            match (value, value.as_f64()) {
+                (serde_json::Value::String(value), _) => value.clone(),
+                (serde_json::Value::Array(values), _) => {
+                    values.iter().map(to_string).collect::<Vec<_>>().join(",")
+                }
+                (_, Some(number)) if value.is_f64() && number.fract() == 0.0 => {
+                    format!("{number:.0}")
+                }
+                _ => value.to_string(),
+            }
+        }
+
+        let mut params = error
+            .params
+            .iter()
+            .map(|(name, value)| {
+                let value = to_string(value);
+                (AttrValue::from(name.to_string()), AttrValue::from(value))
+            })
+            .collect::<Vec<_>>();
+        params.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        Self {
+            code: error.code.to_string().into(),
+            message: error.message.clone().map(Into::into),
+            params,
+        }
+    }
+}
+
+#[cfg(feature = "validator")]
+impl From<ValidationError> for FieldError {
+    fn from(error: ValidationError) -> Self {
+        Self::from(&error)
+    }
+}
+
+/// Serialized form of a [`FieldError`], as `AttrValue` doesn't implement `Serialize`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct FieldErrorData {
+    code: String,
+    message: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<FieldError> for FieldErrorData {
+    fn from(error: FieldError) -> Self {
+        Self {
+            code: error.code.to_string(),
+            message: error.message.map(|message| message.to_string()),
+            params: error
+                .params
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<FieldErrorData> for FieldError {
+    fn from(data: FieldErrorData) -> Self {
+        let error = Self::new(data.code);
+        let error = match data.message {
+            Some(message) => error.with_message(message),
+            None => error,
+        };
+        data.params
+            .into_iter()
+            .fold(error, |error, (name, value)| error.with_param(name, value))
+    }
+}
+
+/// Targets a schema error at fields of the model.
+/// The error is shown as the message of these fields instead of as a message of the model.
+/// ```
+/// # use validator::{Validate, ValidationError};
+/// # use yfb::{prelude::*, validation::TargetFields};
+/// #[derive(PartialEq, Model, Validate)]
+/// #[validate(schema(function = "validate_period"))]
+/// struct Period {
+///     start: u32,
+///     end: u32,
+/// }
+///
+/// fn validate_period(period: &Period) -> Result<(), ValidationError> {
+///     if period.end < period.start {
+///         return Err(ValidationError::new("end_before_start").target_fields(&["end"]));
+///     }
+///     Ok(())
+/// }
+/// ```
+pub trait TargetFields {
+    /// Targets the error at `fields`, named by their identifier regardless of renames
+    fn target_fields(self, fields: &[&str]) -> Self;
+}
+
+impl TargetFields for FieldError {
+    fn target_fields(self, fields: &[&str]) -> Self {
+        self.with_param(TARGET_PARAM, fields.join(","))
+    }
+}
+
+#[cfg(feature = "validator")]
+impl TargetFields for ValidationError {
+    fn target_fields(mut self, fields: &[&str]) -> Self {
+        self.add_param(Cow::Borrowed(TARGET_PARAM), &fields);
+        self
+    }
+}
+
+/// Returns whether `error` was targeted at `field` through [`TargetFields`]
+fn targets(error: &FieldError, field: &str) -> bool {
+    error
+        .param(TARGET_PARAM)
+        .is_some_and(|fields| fields.split(',').any(|f| f == field))
+}
+
+#[doc(hidden)]
+/// Returns the schema errors in `errors` that are not targeted at any field, shown as the messages of the model
+pub fn schema_messages(errors: Option<&ValidationErrors>) -> Vec<FieldError> {
+    schema_errors(errors)
+        .iter()
+        .filter(|error| error.param(TARGET_PARAM).is_none())
+        .cloned()
+        .collect()
+}
+
+#[doc(hidden)]
+/// Returns the errors in `errors` for `field`, or else the schema errors targeted at it
+pub fn field_errors<'a>(
+    errors: Option<&'a ValidationErrors>,
+    field: &str,
+) -> Option<Cow<'a, ValidationErrorsKind>> {
+    if let Some(kind) = errors.and_then(|errors| errors.errors().get(field)) {
+        return Some(Cow::Borrowed(kind));
+    }
+
+    let targeted = schema_errors(errors)
+        .iter()
+        .filter(|error| targets(error, field))
+        .cloned()
+        .collect::<Vec<_>>();
+    (!targeted.is_empty()).then_some(Cow::Owned(ValidationErrorsKind::Field(targeted)))
+}
+
+fn schema_errors(errors: Option<&ValidationErrors>) -> &[FieldError] {
+    match errors.and_then(|errors| errors.errors().get(SCHEMA_KEY)) {
+        Some(ValidationErrorsKind::Field(errors)) => errors,
+        _ => &[],
+    }
+}
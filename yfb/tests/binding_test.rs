@@ -308,3 +308,63 @@ async fn test_binding_invalidation_deps() {
         sleep(std::time::Duration::ZERO).await;
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+#[test]
+async fn test_form_submit() {
+    #[derive(Clone, Debug, Default, PartialEq, Model, Validate)]
+    struct Form {
+        #[validate(length(min = 1, message = "Required"))]
+        name: String,
+    }
+
+    #[function_component(Test)]
+    fn test() -> Html {
+        let binding = use_binding(Form::default);
+        let submitted = use_mut_ref(Vec::new);
+        let submit = {
+            let submitted = submitted.clone();
+            use_form_submit(&binding, move |form: Form| {
+                let submitted = submitted.clone();
+                async move { submitted.borrow_mut().push(form.name) }
+            })
+        };
+        let onclick = {
+            let binding = binding.clone();
+            let submit = submit.clone();
+            Callback::from(move |_| {
+                if submit.submit_count() == 1 {
+                    binding.modifier().name().set("John");
+                }
+                let _ = submit.submit();
+            })
+        };
+
+        html! {
+            <>
+                <div id="button" {onclick}/>
+                <div id="result">
+                    {format!(
+                        "{} {} {} {:?} {:?}",
+                        submit.submit_count(),
+                        submit.submitted(),
+                        submit.rejected(),
+                        binding.state().name.message().as_deref(),
+                        submitted.borrow(),
+                    )}
+                </div>
+            </>
+        }
+    }
+
+    render::<Test>().await;
+    let elem = get_element_by_id::<HtmlElement>("button");
+
+    assert_eq!(get_result(), "0 false false None []");
+    elem.click();
+    sleep(std::time::Duration::ZERO).await;
+    assert_eq!(get_result(), "1 false true Some(\"Required\") []");
+    elem.click();
+    sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(get_result(), "2 true false None [\"John\"]");
+}
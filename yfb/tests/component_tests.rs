@@ -246,34 +246,6 @@ async fn test_select() {
 
 #[test]
 async fn test_textarea() {
-    #[derive(Copy, Clone, PartialEq)]
-    enum TestEnum {
-        A,
-        B,
-    }
-
-    impl Display for TestEnum {
-        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-            match self {
-                Self::A => write!(f, "A"),
-                Self::B => write!(f, "B"),
-            }
-        }
-    }
-
-    impl FromStr for TestEnum {
-        type Err = &'static str;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s {
-                "A" => Ok(Self::A),
-                "B" => Ok(Self::B),
-                _ => Err("Invalid value"),
-            }
-        }
-    }
-
-    impl ValueMarker for TestEnum {}
-
     #[function_component(Test)]
     pub fn test() -> Html {
         let binding = use_named_binding("value", || "test".to_string());
@@ -331,3 +303,195 @@ async fn test_textarea() {
         );
     }
 }
+
+#[test]
+async fn test_error_summary() {
+    #[derive(Clone, PartialEq, Model, Validate)]
+    struct Model {
+        #[validate(length(min = 3))]
+        name: String,
+        #[validate(range(min = 18))]
+        age: u32,
+        #[validate(email)]
+        email: String,
+    }
+
+    #[function_component(Test)]
+    pub fn test() -> Html {
+        let binding = use_binding(|| Model {
+            name: "ab".into(),
+            age: 16,
+            email: "test@example.com".into(),
+        });
+        let _ = binding.validate();
+
+        html! {
+            <ErrorSummary<Model> binding={&binding} title="There is a problem"/>
+        }
+    }
+
+    #[function_component(Expected)]
+    fn expected() -> Html {
+        html! {
+            <div role="alert">
+                <h2>{"There is a problem"}</h2>
+                <ul>
+                    <li><a href="#model.name">{"Must be at least 3 characters long"}</a></li>
+                    <li><a href="#model.age">{"Must be at least 18"}</a></li>
+                </ul>
+            </div>
+        }
+    }
+
+    assert_eq!(render::<Test>().await, render::<Expected>().await);
+}
+
+#[test]
+async fn test_field_message() {
+    #[derive(Clone, PartialEq, Model, Validate)]
+    struct Model {
+        #[validate(length(min = 3))]
+        name: String,
+        #[validate(range(min = 18))]
+        age: u32,
+    }
+
+    #[function_component(Test)]
+    pub fn test() -> Html {
+        let binding = use_binding(|| Model {
+            name: "ab".into(),
+            age: 16,
+        });
+        binding.age_binding().modifier().set("abc");
+        let _ = binding.validate();
+
+        html! {
+            <>
+                <Label<String> binding={binding.name_binding()}>{"Name"}</Label<String>>
+                <Input<String> binding={binding.name_binding()} described_by_message=true/>
+                <FieldMessage<String> binding={binding.name_binding()} classes="message"/>
+                <FieldMessage<u32> binding={binding.age_binding()} classes="message" classes_error="error"/>
+                <FieldMessage<u32> binding={binding.age_binding()} kind={MessageKind::Message}/>
+            </>
+        }
+    }
+
+    #[function_component(Expected)]
+    fn expected() -> Html {
+        html! {
+            <>
+                <label for="model.name">{"Name"}</label>
+                <input
+                    id="model.name"
+                    name="model.name"
+                    type="text"
+                    autocomplete="off"
+                    aria-describedby="model.name-message"
+                    value="ab"
+                />
+                <div id="model.name-message" class="message">{"Must be at least 3 characters long"}</div>
+                <div id="model.age-message" class="message error">{"Invalid value 'abc'"}</div>
+                <div id="model.age-message">{"Must be at least 18"}</div>
+            </>
+        }
+    }
+
+    assert_eq!(render::<Test>().await, render::<Expected>().await);
+}
+
+#[test]
+async fn test_input_without_message() {
+    #[derive(Clone, PartialEq, Model, Validate)]
+    struct Model {
+        #[validate(length(min = 3))]
+        name: String,
+    }
+
+    #[function_component(Test)]
+    pub fn test() -> Html {
+        let binding = use_binding(|| Model { name: "ab".into() });
+        let _ = binding.validate();
+
+        html! {
+            <Input<String> binding={binding.name_binding()}/>
+        }
+    }
+
+    #[function_component(Expected)]
+    fn expected() -> Html {
+        html! {
+            <input
+                id="model.name"
+                name="model.name"
+                type="text"
+                autocomplete="off"
+                value="ab"
+            />
+        }
+    }
+
+    assert_eq!(render::<Test>().await, render::<Expected>().await);
+}
+
+#[test]
+async fn test_radio_group() {
+    #[function_component(Test)]
+    pub fn test() -> Html {
+        let binding = use_named_binding("value", || "small".to_string());
+
+        html! {
+            <>
+                <RadioGroup<String> binding={&binding}>
+                    <Radio value="small">{"Small"}</Radio>
+                    <Radio value="large"/>
+                    <Radio value="huge" disabled=true/>
+                </RadioGroup<String>>
+                <p>{&binding.model()}</p>
+            </>
+        }
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct ExpectedProps {
+        value: String,
+    }
+
+    #[function_component(Expected)]
+    fn expected(ExpectedProps { value }: &ExpectedProps) -> Html {
+        html! {
+            <>
+                <div id="value" role="radiogroup">
+                    <input type="radio" id="value-0" name="value" value="small" checked={value == "small"}/>
+                    <label for="value-0">{"Small"}</label>
+                    <input type="radio" id="value-1" name="value" value="large" checked={value == "large"}/>
+                    <label for="value-1">{"large"}</label>
+                    <input type="radio" id="value-2" name="value" value="huge" disabled=true/>
+                    <label for="value-2">{"huge"}</label>
+                </div>
+                <p>{value}</p>
+            </>
+        }
+    }
+
+    let expected = render_with_props::<Expected>(ExpectedProps {
+        value: "small".into(),
+    })
+    .await;
+    assert_eq!(render::<Test>().await, expected);
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let elem = get_element_by_id::<HtmlInputElement>("value-1");
+        elem.set_checked(true);
+        elem.dispatch_event(&Event::new("change").unwrap()).unwrap();
+        sleep(Duration::ZERO).await;
+
+        assert_eq!(
+            common::get_output(),
+            render_with_props::<Expected>(ExpectedProps {
+                value: "large".into()
+            })
+            .await
+        );
+    }
+}
@@ -1,34 +1,173 @@
-use darling::{ast::Data, FromDeriveInput, FromField, FromMeta, ToTokens};
+mod variants;
+
+use darling::{
+    ast::{Data, Fields, Style},
+    FromDeriveInput, FromField, FromMeta, FromVariant, ToTokens,
+};
 use inflector::Inflector;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, DeriveInput, Ident, Path, Type, Visibility};
+use syn::{parse_macro_input, DeriveInput, Ident, Member, Path, Type, Visibility};
 
+/// Derives `Model` for structs and enums.
+///
+/// Container attributes:
+/// - `#[yfb(rename = "...")]`: root name of the model's bindings, defaults to the snake_case type name
+/// - `#[yfb(rename_all = "...")]`: naming strategy for the model's variants and fields, accepts the same rules as serde.
+///   The root name is only changed by `rename`
+/// - `#[yfb(no_validate)]`: implements `Validator` for a model without any validation, so it doesn't need to derive `validator::Validate`
+/// - `#[yfb(garde)]`: implements `Validator` through `garde::Validate`, for models validated with `garde` rather than `validator`.
+///   Requires yfb's `garde` feature and a `garde` context implementing `Default`
+/// - `#[yfb(default)]`: creates the struct with its `Default` implementation when it is decoded from a single path, e.g. as a new item of a list
+///
+/// Variant attributes:
+/// - `#[yfb(rename = "...")]`: name of the variant in binding names
+///
+/// Field attributes:
+/// - `#[yfb(rename = "...")]`: name of the field in binding names
+/// - `#[yfb(skip)]`: excludes the field from the state, modifier and bindings.
+///   Skipped fields of enum variants need `Default`, as switching to their variant creates them
+/// - `#[yfb(no_dirty)]`: keeps the field bound, but ignores it when determining whether the model is dirty
+/// - `#[yfb(flatten)]`: exposes the bindings and modifier methods of a nested model on this one, including those of the models it flattens in turn,
+///   without adding the field's name to their binding names.
+///   The nested model's `BindingExt`/`ModifierExt` traits need to be in scope
+/// - `#[yfb(async_validator = "...")]`: path to a function taking a copy of the field's value and returning a future of `Result<(), E>`,
+///   where `E` converts into `yfb::validation::FieldError`, e.g. `validator::ValidationError`.
+///   It runs once the value passed the synchronous validation, only the result for the latest value is kept. Only supported on named struct fields
+/// - `#[yfb(debounce_ms = ...)]`: delays updating the field's message and running its asynchronous validator until the value has not changed for this many milliseconds.
+///   The value itself is still updated immediately. Only supported on named struct fields of a `Value` type
 #[proc_macro_derive(Model, attributes(yfb))]
 pub fn derive_model(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
     match ModelReceiver::from_derive_input(&ast) {
-        Ok(receiver) => receiver.to_token_stream(),
+        Ok(mut receiver) => {
+            receiver.resolve_fields();
+            receiver.to_token_stream()
+        }
         Err(e) => e.write_errors(),
     }
     .into()
 }
+
 #[derive(Debug, FromField)]
+#[darling(attributes(yfb))]
 struct ModelField {
     ident: Option<Ident>,
     vis: Visibility,
     ty: Type,
+    /// Excludes the field from the generated state, mappings, modifier and bindings
+    #[darling(default)]
+    skip: bool,
+    /// Keeps the field bound, but ignores it when determining whether the model is dirty
+    #[darling(default)]
+    no_dirty: bool,
+    /// Embeds the fields of a nested model into this one, without adding a segment to their binding names
+    #[darling(default)]
+    flatten: bool,
+    /// Overrides the name of the field in binding names
+    rename: Option<String>,
+    /// Asynchronous validator run once the field passed its synchronous validation
+    async_validator: Option<Path>,
+    /// Delays the validation of the field until its value has not changed for this many milliseconds
+    debounce_ms: Option<u64>,
+    /// Position of the field within its struct or variant, assigned by [`ModelReceiver::resolve_fields()`]
+    #[darling(skip)]
+    index: usize,
+}
+
+impl ModelField {
+    /// The field as used for accessing it, the index for tuple fields
+    fn member(&self) -> Member {
+        match &self.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(self.index.into()),
+        }
+    }
+
+    /// Name of the field as declared, the index for tuple fields
+    fn ident_name(&self) -> String {
+        match &self.ident {
+            Some(ident) => ident.to_string(),
+            None => self.index.to_string(),
+        }
+    }
+
+    /// Name of the field used in binding names
+    fn name(&self) -> String {
+        self.rename.clone().unwrap_or_else(|| self.ident_name())
+    }
+
+    /// Name of the first attribute that only applies to fields of validated structs, if any
+    fn validation_attribute(&self) -> Option<&'static str> {
+        if self.async_validator.is_some() {
+            Some("async_validator")
+        } else if self.debounce_ms.is_some() {
+            Some("debounce_ms")
+        } else {
+            None
+        }
+    }
+
+    /// Expression telling whether the field's validation should be applied now, see [`ModelField::debounce_ms`]
+    fn settled(&self) -> TokenStream {
+        match self.debounce_ms {
+            Some(_) => format_ident!("__settled_{}", self.ident_name()).into_token_stream(),
+            None => quote!(true),
+        }
+    }
+
+    /// Identifier for the generated modifier and binding methods, `_0` for tuple fields
+    fn method_ident(&self) -> Ident {
+        match &self.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("_{}", self.index),
+        }
+    }
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(yfb))]
+struct ModelVariant {
+    ident: Ident,
+    fields: Fields<ModelField>,
+    /// Overrides the name of the variant in binding names
+    rename: Option<String>,
+}
+
+impl ModelVariant {
+    /// Name of the variant used in binding names
+    fn name(&self) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| self.ident.to_string().to_snake_case())
+    }
 }
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(yfb), supports(struct_named))]
+#[darling(
+    attributes(yfb),
+    supports(struct_named, struct_newtype, struct_tuple, enum_any)
+)]
 struct ModelReceiver {
     ident: Ident,
     vis: Visibility,
-    data: Data<(), ModelField>,
+    data: Data<ModelVariant, ModelField>,
     #[darling(default)]
     path: CratePath,
+    /// Overrides the root name of the model's bindings
+    rename: Option<String>,
+    /// Naming strategy for the variants and fields, unless they are renamed explicitly
+    rename_all: Option<RenameRule>,
+    /// Implements `Validator` for the model, accepting any value
+    #[darling(default)]
+    no_validate: bool,
+    /// Implements `Validator` for the model through `garde::Validate`
+    #[darling(default)]
+    garde: bool,
+    /// Creates the struct through `Default` in `ModelState::from_path`
+    #[darling(default)]
+    default: bool,
 }
 
 #[derive(Debug, FromMeta)]
@@ -46,43 +185,298 @@ impl ToTokens for CratePath {
     }
 }
 
+/// Same naming strategies as serde's `rename_all`
+#[derive(Debug, Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => name.to_pascal_case(),
+            RenameRule::Camel => name.to_camel_case(),
+            RenameRule::Snake => name.to_snake_case(),
+            RenameRule::ScreamingSnake => name.to_screaming_snake_case(),
+            RenameRule::Kebab => name.to_kebab_case(),
+            RenameRule::ScreamingKebab => name.to_kebab_case().to_uppercase(),
+        }
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(match value {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return Err(darling::Error::unknown_value(value)),
+        })
+    }
+}
+
 impl ToTokens for ModelReceiver {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ident = &self.ident;
         let yfb = &self.path;
-        let fields = self
-            .data
-            .as_ref()
-            .take_struct()
-            .expect("only named structs are supported")
-            .fields;
-
-        let (state, state_ident) = self.expand_state(&fields);
-        let (mappings, mapping_idents) = self.expand_mappings(&fields);
-        let (modifier, modifier_ident) = self.expand_modifier(&fields, &mapping_idents);
-        let binding_ext = self.expand_binding_ext(&fields, &mapping_idents);
-        let model_name = ident.to_string().to_snake_case();
+        // Like serde, `rename_all` only applies to the variants and fields
+        let model_name = self
+            .rename
+            .clone()
+            .unwrap_or_else(|| ident.to_string().to_snake_case());
 
         tokens.extend(quote! {
             impl #yfb::model::Model for #ident {
                 const NAME: &'static str = #model_name;
             }
+        });
+
+        let validate = match (self.no_validate, self.garde) {
+            (true, true) => {
+                tokens.extend(
+                    syn::Error::new_spanned(ident, "`no_validate` and `garde` can't be combined")
+                        .to_compile_error(),
+                );
+                return;
+            }
+            (true, false) => Some(quote!(::std::result::Result::Ok(()))),
+            (false, true) => Some(quote!(#yfb::model::validate_garde(self))),
+            (false, false) => None,
+        };
+        if let Some(validate) = validate {
+            tokens.extend(quote! {
+                impl #yfb::model::Validator for #ident {
+                    fn validate(&self) -> ::std::result::Result<(), #yfb::validation::ValidationErrors> {
+                        #validate
+                    }
+                }
+            });
+        }
+
+        match &self.data {
+            Data::Struct(fields) => self.expand_struct(fields, tokens),
+            Data::Enum(variants) => self.expand_enum(variants, tokens),
+        }
+    }
+}
+
+impl ModelReceiver {
+    /// Derives `Serialize`/`Deserialize` for a generated type when yfb's `serde` feature is enabled
+    fn derive_serde(&self) -> TokenStream {
+        if !cfg!(feature = "serde") {
+            return TokenStream::new();
+        }
+
+        let yfb = &self.path;
+        let serde = quote!(#yfb::serde).to_string().replace(' ', "");
+        quote! {
+            #[derive(#yfb::serde::Serialize, #yfb::serde::Deserialize)]
+            #[serde(crate = #serde)]
+        }
+    }
+
+    /// Leaves a field of a generated type out of serialization, see [`Self::derive_serde`]
+    fn skip_serde(&self) -> TokenStream {
+        match cfg!(feature = "serde") {
+            true => quote!(#[serde(skip)]),
+            false => TokenStream::new(),
+        }
+    }
+
+    /// Assigns each field its index and applies `rename_all` to fields and variants that aren't renamed
+    fn resolve_fields(&mut self) {
+        let rename_all = self.rename_all;
+        let rename = |rename: &mut Option<String>, ident: Option<&Ident>| {
+            if let (None, Some(ident), Some(rule)) = (&rename, ident, rename_all) {
+                *rename = Some(rule.apply(&ident.to_string()));
+            }
+        };
+
+        let fields = match &mut self.data {
+            Data::Struct(fields) => vec![fields],
+            Data::Enum(variants) => variants
+                .iter_mut()
+                .map(|v| {
+                    rename(&mut v.rename, Some(&v.ident));
+                    &mut v.fields
+                })
+                .collect(),
+        };
+
+        for (index, field) in fields
+            .into_iter()
+            .flat_map(|f| f.fields.iter_mut().enumerate())
+        {
+            field.index = index;
+            rename(&mut field.rename, field.ident.as_ref());
+        }
+    }
+
+    fn expand_struct(&self, fields: &Fields<ModelField>, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+        let yfb = &self.path;
+        let style = fields.style;
+        let skipped = fields.iter().any(|f| f.skip);
+        let fields = fields.iter().filter(|f| !f.skip).collect::<Vec<_>>();
+
+        if let Some((field, attribute)) = fields
+            .iter()
+            .filter(|_| !style.is_struct())
+            .find_map(|f| f.validation_attribute().map(|attribute| (f, attribute)))
+        {
+            tokens.extend(
+                syn::Error::new_spanned(
+                    &field.ty,
+                    format!("`{attribute}` is only supported on named struct fields"),
+                )
+                .to_compile_error(),
+            );
+            return;
+        }
+
+        let (state, state_ident) = self.expand_state(&fields, style, skipped);
+        let (mappings, mapping_idents) = self.expand_mappings(&fields);
+        let (modifier, modifier_ident) = self.expand_modifier(&fields);
+
+        let (flattened, accessors) = fields
+            .iter()
+            .zip(&mapping_idents)
+            .partition::<Vec<_>, _>(|(f, _)| f.flatten);
+        let flattened = flattened
+            .into_iter()
+            .map(|(f, m)| self.expand_flatten(f, m));
+        let accessors = accessors
+            .into_iter()
+            .map(|(f, m)| Accessor {
+                ident: f.method_ident(),
+                ty: &f.ty,
+                mapping: m.clone(),
+                variant: false,
+            })
+            .collect::<Vec<_>>();
+        let accessors = self.expand_accessors(&accessors, &modifier_ident);
 
+        let values = self.expand_values(&fields);
+
+        tokens.extend(quote! {
             impl #yfb::model::ModelState for #ident {
                 type State = #state_ident;
                 type Modifier = #modifier_ident;
+
+                #values
             }
 
             #state
             #modifier
             #(#mappings)*
-            #binding_ext
+            #accessors
+            #(#flattened)*
         });
     }
-}
 
-impl ModelReceiver {
-    fn expand_state(&self, fields: &[&ModelField]) -> (TokenStream, Ident) {
+    /// Walks the values of a struct by their path, the fields of flattened models share the path of the struct
+    fn expand_values(&self, fields: &[&ModelField]) -> TokenStream {
+        let yfb = &self.path;
+
+        let visit_fields = fields.iter().map(|f| {
+            let member = f.member();
+            match f.flatten {
+                true => quote! {
+                    #yfb::model::ModelState::visit_values(&self.#member, path, visit);
+                },
+                false => {
+                    let name = format!(".{}", f.name());
+                    quote! {
+                        #yfb::model::ModelState::visit_values(
+                            &self.#member,
+                            &::std::format!("{}{}", path, #name),
+                            visit,
+                        );
+                    }
+                }
+            }
+        });
+
+        let (flattened, named) = fields
+            .iter()
+            .partition::<Vec<&&ModelField>, _>(|f| f.flatten);
+        let flattened = flattened.iter().map(|f| f.member());
+        let (names, members) = named
+            .iter()
+            .map(|f| (f.name(), f.member()))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let from_path = match self.default {
+            true => quote! {
+                let mut model = <Self as ::std::default::Default>::default();
+                #yfb::model::ModelState::assign_value(&mut model, path, value)?;
+                ::std::result::Result::Ok(model)
+            },
+            false => quote! {
+                let _ = (path, value);
+                ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath)
+            },
+        };
+
+        quote! {
+            fn visit_values(
+                &self,
+                path: &::std::primitive::str,
+                visit: &mut dyn ::std::ops::FnMut(&::std::primitive::str, #yfb::yew::AttrValue),
+            ) {
+                #(#visit_fields)*
+            }
+
+            fn assign_value(
+                &mut self,
+                path: &::std::primitive::str,
+                value: &#yfb::yew::AttrValue,
+            ) -> ::std::result::Result<(), #yfb::model::AssignError> {
+                #(
+                    match #yfb::model::ModelState::assign_value(&mut self.#flattened, path, value) {
+                        ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath) => {}
+                        result => return result,
+                    }
+                )*
+                match #yfb::model::split_field(path) {
+                    #(
+                        ::std::option::Option::Some((#names, rest)) => {
+                            #yfb::model::ModelState::assign_value(&mut self.#members, rest, value)
+                        }
+                    )*
+                    _ => ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath),
+                }
+            }
+
+            fn from_path(
+                path: &::std::primitive::str,
+                value: &#yfb::yew::AttrValue,
+            ) -> ::std::result::Result<Self, #yfb::model::AssignError> {
+                #from_path
+            }
+        }
+    }
+
+    fn expand_state(
+        &self,
+        fields: &[&ModelField],
+        style: Style,
+        skipped: bool,
+    ) -> (TokenStream, Ident) {
         let yfb = &self.path;
         let vis = &self.vis;
         let model_ident = &self.ident;
@@ -91,33 +485,96 @@ impl ModelReceiver {
         let state_fields = fields
             .iter()
             .map(|f| {
-                let ident = f.ident.as_ref().unwrap();
                 let ty = &f.ty;
                 let vis = &f.vis;
-                quote! {
-                    #vis #ident: <#ty as #yfb::model::ModelState>::State
+                match &f.ident {
+                    Some(ident) => quote! {
+                        #vis #ident: <#ty as #yfb::model::ModelState>::State
+                    },
+                    None => quote! {
+                        #vis <#ty as #yfb::model::ModelState>::State
+                    },
                 }
             })
             .collect::<Vec<_>>();
 
+        let (state_struct, messages) = match style {
+            Style::Tuple => (
+                quote! {
+                    #vis struct #state_ident(#(#state_fields,)* #yfb::model::Messages);
+                },
+                Member::Unnamed(fields.len().into()),
+            ),
+            _ => (
+                quote! {
+                    #vis struct #state_ident {
+                        #(#state_fields,)*
+                        __messages: #yfb::model::Messages,
+                    }
+                },
+                Member::Named(format_ident!("__messages")),
+            ),
+        };
+
         let (field_idents, field_types) = fields
             .iter()
-            .map(|f| (f.ident.as_ref().unwrap(), &f.ty))
+            .map(|f| (f.member(), &f.ty))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
+        let dirty_idents = fields
+            .iter()
+            .filter(|f| !f.no_dirty)
+            .map(|f| f.member())
+            .collect::<Vec<_>>();
+
+        // Validators report errors by the field's identifier, regardless of renames
+        let field_names = fields.iter().map(|f| f.ident_name());
+
+        // Skipped fields have no state to build them from
+        let initial_model = match style {
+            _ if skipped => quote!(::std::option::Option::None),
+            Style::Tuple => quote! {
+                ::std::option::Option::Some(#model_ident(
+                    #( #yfb::model::State::<#field_types>::initial_model(&self.#field_idents)?, )*
+                ))
+            },
+            _ => quote! {
+                ::std::option::Option::Some(#model_ident {
+                    #( #field_idents: #yfb::model::State::<#field_types>::initial_model(&self.#field_idents)?, )*
+                })
+            },
+        };
+
+        let visit_fields = fields.iter().map(|f| {
+            let member = f.member();
+            match f.flatten {
+                true => quote! {
+                    #yfb::model::Visit::visit(&self.#member, path, visitor);
+                },
+                false => {
+                    let name = format!(".{}", f.name());
+                    quote! {
+                        #yfb::model::Visit::visit(&self.#member, &::std::format!("{}{}", path, #name), visitor);
+                    }
+                }
+            }
+        });
+
+        let derive_serde = self.derive_serde();
+
         (
             quote! {
                 #[derive(Debug, PartialEq)]
-                #vis struct #state_ident {
-                    #(#state_fields),*
-                }
+                #derive_serde
+                #state_struct
 
                 impl #yfb::model::State<#model_ident> for #state_ident {
                     fn create(model: &#model_ident,
                               with_initial: ::std::primitive::bool,
                               generation: #yfb::hooks::UseGenerationHandle) -> Self {
                         Self {
-                            #(#field_idents: #yfb::model::State::create(&model.#field_idents, with_initial, generation.clone())),*
+                            #(#field_idents: #yfb::model::State::create(&model.#field_idents, with_initial, generation.clone()),)*
+                            #messages: #yfb::model::Messages::new(generation),
                         }
                     }
 
@@ -128,9 +585,80 @@ impl ModelReceiver {
                     }
 
                     fn generation(&self) -> usize {
-                        [#(
-                            #yfb::model::State::<#field_types>::generation(&self.#field_idents)
-                        ),*].into_iter().max().unwrap_or_default()
+                        [
+                            self.#messages.generation(),
+                            #(
+                                #yfb::model::State::<#field_types>::generation(&self.#field_idents),
+                            )*
+                        ].into_iter().max().unwrap_or_default()
+                    }
+
+                    fn attach(&mut self, generation: #yfb::hooks::UseGenerationHandle) {
+                        #(
+                            #yfb::model::State::<#field_types>::attach(&mut self.#field_idents, ::std::clone::Clone::clone(&generation));
+                        )*
+                        self.#messages.attach(generation);
+                    }
+
+                    fn restore_initial(&self, model: &mut #model_ident) -> ::std::primitive::bool {
+                        false
+                        #(
+                            | #yfb::model::State::<#field_types>::restore_initial(&self.#field_idents, &mut model.#field_idents)
+                        )*
+                    }
+
+                    fn initial_model(&self) -> ::std::option::Option<#model_ident> {
+                        #initial_model
+                    }
+                }
+
+                impl #yfb::model::SetErrors for #state_ident {
+                    fn set_errors(
+                        &mut self,
+                        errors: ::std::option::Option<&#yfb::validation::ValidationErrorsKind>,
+                        all: ::std::primitive::bool,
+                    ) {
+                        let errors = match errors {
+                            ::std::option::Option::Some(#yfb::validation::ValidationErrorsKind::Struct(errors)) => {
+                                ::std::option::Option::Some(&**errors)
+                            }
+                            _ => ::std::option::Option::None,
+                        };
+
+                        #(
+                            #yfb::model::SetErrors::set_errors(
+                                &mut self.#field_idents,
+                                #yfb::validation::field_errors(errors, #field_names).as_deref(),
+                                all,
+                            );
+                        )*
+
+                        let messages = #yfb::validation::schema_messages(errors);
+                        if messages.is_empty() || all || #yfb::model::Dirty::dirty(self) {
+                            self.#messages.set(messages);
+                        }
+                    }
+
+                    fn clear_errors(&mut self) {
+                        #( #yfb::model::SetErrors::clear_errors(&mut self.#field_idents); )*
+                        self.#messages.set(::std::vec::Vec::new());
+                    }
+                }
+
+                impl #yfb::model::ModelMessages for #state_ident {
+                    fn messages(&self) -> &#yfb::model::Messages {
+                        &self.#messages
+                    }
+
+                    fn messages_mut(&mut self) -> &mut #yfb::model::Messages {
+                        &mut self.#messages
+                    }
+                }
+
+                impl #yfb::model::Visit for #state_ident {
+                    fn visit(&self, path: &::std::primitive::str, visitor: &mut dyn #yfb::model::Visitor) {
+                        visitor.messages(path, &self.#messages);
+                        #(#visit_fields)*
                     }
                 }
 
@@ -138,7 +666,38 @@ impl ModelReceiver {
                     fn dirty(&self) -> ::std::primitive::bool {
                         false
                         #(
-                            | #yfb::model::Dirty::dirty(&self.#field_idents)
+                            | #yfb::model::Dirty::dirty(&self.#dirty_idents)
+                        )*
+                    }
+                }
+
+                impl #yfb::model::Touched for #state_ident {
+                    fn touched(&self) -> ::std::primitive::bool {
+                        false
+                        #(
+                            | #yfb::model::Touched::touched(&self.#field_idents)
+                        )*
+                    }
+
+                    fn focused(&self) -> ::std::primitive::bool {
+                        false
+                        #(
+                            | #yfb::model::Touched::focused(&self.#field_idents)
+                        )*
+                    }
+
+                    fn touch(&mut self) {
+                        #(
+                            #yfb::model::Touched::touch(&mut self.#field_idents);
+                        )*
+                    }
+                }
+
+                impl #yfb::model::Validating for #state_ident {
+                    fn validating(&self) -> ::std::primitive::bool {
+                        false
+                        #(
+                            | #yfb::model::Validating::validating(&self.#field_idents)
                         )*
                     }
                 }
@@ -154,13 +713,13 @@ impl ModelReceiver {
         fields
             .iter()
             .map(|f| {
-                let ident = f.ident.as_ref().unwrap();
+                let ident = f.member();
+                let ident_name = f.name();
                 let mapping_ident = format_ident!(
                     "{}{}Mapping",
                     model_ident,
-                    ident.to_string().to_pascal_case()
+                    f.ident_name().to_pascal_case()
                 );
-                let ident_name = ident.to_string();
                 let ty = &f.ty;
                 let vis = &f.vis;
                 (
@@ -201,86 +760,133 @@ impl ModelReceiver {
             .unzip()
     }
 
-    fn expand_modifier(&self, fields: &[&ModelField], mappings: &[Ident]) -> (TokenStream, Ident) {
+    fn expand_modifier(&self, fields: &[&ModelField]) -> (TokenStream, Ident) {
         let yfb = &self.path;
         let model_ident = &self.ident;
         let vis = &self.vis;
         let modifier_ident = format_ident!("{}Modifier", model_ident);
-
-        let (field_modifiers, field_set_message) = fields
+        // Validators report errors by the field's identifier, regardless of renames
+        let (field_members, field_names) = fields
             .iter()
-            .zip(mappings)
-            .map(|(f, m)| {
-                let ident = f.ident.as_ref().unwrap();
-                let ident_name = format!("{}", ident);
-                let ty = &f.ty;
-                (
-                    quote! {
-                        #vis fn #ident(&self) -> <#ty as #yfb::model::ModelState>::Modifier {
-                            #yfb::modifier::Modifier::map(&self.0, #m)
-                        }
-                    },
-                    quote! {
-                        #ident_name => {
-                            let modifier = self.#ident();
-                            if #yfb::modifier::Modifier::dirty(&modifier) {
-                                if let Some(error) = <[::validator::ValidationError]>::first(errors) {
-                                    #yfb::modifier::Modifier::set_message(&modifier, error.message.clone().map(|m| m.into()));
-                                }
-                            }
-                        },
-                    },
-                )
-            })
+            .map(|f| (f.member(), f.ident_name()))
             .unzip::<_, _, Vec<_>, Vec<_>>();
 
-        let field_idents = fields
+        let async_fields = fields
             .iter()
-            .map(|f| f.ident.as_ref().unwrap())
+            .filter(|f| f.async_validator.is_some())
             .collect::<Vec<_>>();
+        let async_idents = async_fields
+            .iter()
+            .map(|f| f.method_ident())
+            .collect::<Vec<_>>();
+        let async_names = async_idents
+            .iter()
+            .map(Ident::to_string)
+            .collect::<Vec<_>>();
+        let async_validators = async_fields.iter().map(|f| &f.async_validator);
+        let async_settled = async_fields.iter().map(|f| f.settled());
+        let settled = fields.iter().map(|f| f.settled());
+
+        let debounced = fields.iter().filter_map(|f| {
+            let debounce_ms = f.debounce_ms?;
+            let ident = f.method_ident();
+            let settled = f.settled();
+            Some(quote! {
+                let #settled = all || self.#ident().debounce(#debounce_ms, {
+                    let state_model = ::std::clone::Clone::clone(#yfb::modifier::Modifier::state_model(&self.0));
+                    // Dropping the modifier validates the model again
+                    move || ::std::mem::drop(<Self as #yfb::modifier::Modifier<#model_ident>>::create(state_model))
+                });
+            })
+        });
+
+        let validate_async = (!async_fields.is_empty()).then(|| {
+            quote! {
+                // Asynchronous validators only run for values that passed the synchronous validation
+                let async_errors = [#({
+                    let modifier = self.#async_idents();
+                    if #async_settled
+                        && (all || #yfb::modifier::Modifier::dirty(&modifier))
+                        && !#yfb::validation::ValidationErrors::has_error(&validation, #async_names)
+                    {
+                        modifier.validate_async(#async_validators);
+                    }
+                    modifier.async_error().map(|error| (#async_names, error))
+                }),*];
+
+                for (name, error) in async_errors.into_iter().flatten() {
+                    match &mut validation {
+                        ::std::result::Result::Err(errors) => errors.add(name, error),
+                        ::std::result::Result::Ok(()) => {
+                            let mut errors = #yfb::validation::ValidationErrors::new();
+                            errors.add(name, error);
+                            validation = ::std::result::Result::Err(errors);
+                        }
+                    }
+                }
+            }
+        });
+
+        let validate = quote! {
+            fn validate(&self, all: ::std::primitive::bool) -> ::std::result::Result<(), #yfb::validation::ValidationErrors> {
+                #[allow(unused_mut)]
+                let mut validation = {
+                    let state_model = #yfb::modifier::Modifier::state_model(&self.0);
+                    #yfb::model::Validator::validate(&*state_model.model())
+                };
+                #(#debounced)*
+
+                {
+                    let errors = validation.as_ref().err();
+                    let (_, mut state) = #yfb::modifier::Modifier::state_model(&self.0).as_mut();
+
+                    #(
+                        if #settled {
+                            #yfb::model::SetErrors::set_errors(
+                                &mut state.#field_members,
+                                #yfb::validation::field_errors(errors, #field_names).as_deref(),
+                                all,
+                            );
+                        }
+                    )*
+
+                    let messages = #yfb::validation::schema_messages(errors);
+                    if messages.is_empty() || all || #yfb::model::Dirty::dirty(&*state) {
+                        #yfb::model::ModelMessages::messages_mut(&mut *state).set(messages);
+                    }
+                }
+
+                #validate_async
+
+                validation
+            }
+        };
 
         (
             quote! {
-                #vis struct #modifier_ident(#yfb::modifier::BaseModifier<#model_ident>);
+                #vis struct #modifier_ident(#yfb::modifier::BaseModifier<#model_ident>, ::std::primitive::usize);
 
                 impl #yfb::modifier::Modifier<#model_ident> for #modifier_ident {
                     fn create(state_model: #yfb::state_model::StateModelRc<#model_ident>) -> Self {
-                        Self(#yfb::modifier::Modifier::create(state_model))
+                        let generation = #yfb::model::State::<#model_ident>::generation(
+                            &*#yfb::state_model::StateModel::state(&*state_model),
+                        );
+                        Self(#yfb::modifier::Modifier::create(state_model), generation)
                     }
 
                     fn state_model(&self) -> &#yfb::state_model::StateModelRc<#model_ident> {
                         #yfb::modifier::Modifier::state_model(&self.0)
                     }
-                }
 
-                impl #modifier_ident {
-                    #(#field_modifiers)*
+                    #validate
                 }
 
-                impl Drop for #modifier_ident  {
+                // Fields excluded from `Dirty` are validated too, so the modifier also validates when the state changed while it was alive
+                impl Drop for #modifier_ident {
                     fn drop(&mut self) {
-                        if !#yfb::modifier::Modifier::dirty(self) {
-                            return;
-                        }
-
-                        let validation = {
-                            let state_model = #yfb::modifier::Modifier::state_model(&self.0);
-                            ::validator::Validate::validate(&*state_model.model())
-                        };
-
-                        #(
-                            #yfb::modifier::Modifier::set_message(&self.#field_idents(), None);
-                        )*
-
-                        let Err(errors) = validation else {
-                            return;
-                        };
-
-                        for (field, errors) in errors.field_errors() {
-                            match field {
-                                #(#field_set_message)*
-                                _ => {}
-                            }
+                        let changed = #yfb::model::State::<#model_ident>::generation(&*#yfb::modifier::Modifier::state(self)) != self.1;
+                        if changed || #yfb::modifier::Modifier::dirty(self) {
+                            let _ = #yfb::modifier::Modifier::validate(self, false);
                         }
                     }
                 }
@@ -289,39 +895,143 @@ impl ModelReceiver {
         )
     }
 
-    fn expand_binding_ext(&self, fields: &[&ModelField], mappings: &[Ident]) -> TokenStream {
+    /// Expands the modifier methods and the binding extension trait for the model's fields.
+    /// Both are also implemented for models that flatten this one, through `Flatten` and `FlattenModifier`.
+    fn expand_accessors(&self, accessors: &[Accessor], modifier_ident: &Ident) -> TokenStream {
         let yfb = &self.path;
         let model_ident = &self.ident;
-        let trait_ident = format_ident!("{}BindingExt", self.ident);
+        let modifier_ext_ident = format_ident!("{}ModifierExt", model_ident);
+        let binding_ext_ident = format_ident!("{}BindingExt", model_ident);
         let vis = &self.vis;
-        let (binding_fn_defs, binding_fns) = fields
-            .iter()
-            .zip(mappings)
-            .map(|(f, m)| {
-                let fn_ident = format_ident!("{}_binding", &f.ident.as_ref().unwrap());
-                let ty = &f.ty;
 
-                (
-                    quote! {
-                        fn #fn_ident(&self) -> #yfb::binding::Binding<#ty>;
-                    },
-                    quote! {
-                        fn #fn_ident(&self) -> #yfb::binding::Binding<#ty> {
-                            #yfb::binding::Binding::map(self, #m)
-                        }
-                    },
-                )
-            })
-            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let mut modifier_fns = Vec::new();
+        let mut modifier_fn_defs = Vec::new();
+        let mut modifier_ext_fns = Vec::new();
+        let mut binding_fn_defs = Vec::new();
+        let mut binding_fns = Vec::new();
+
+        for Accessor {
+            ident,
+            ty,
+            mapping,
+            variant,
+        } in accessors
+        {
+            let binding_ident = format_ident!("{}_binding", ident);
+            let (map, state_model) = match variant {
+                false => (quote!(map), quote!(MappedStateModel)),
+                true => (quote!(map_variant), quote!(MappedVariantStateModel)),
+            };
+
+            modifier_fns.push(quote! {
+                #vis fn #ident(&self) -> <#ty as #yfb::model::ModelState>::Modifier {
+                    #yfb::modifier::Modifier::#map(&self.0, #mapping)
+                }
+            });
+            modifier_fn_defs.push(quote! {
+                fn #ident(&self) -> <#ty as #yfb::model::ModelState>::Modifier;
+            });
+            modifier_ext_fns.push(quote! {
+                fn #ident(&self) -> <#ty as #yfb::model::ModelState>::Modifier {
+                    #yfb::modifier::Modifier::create(::std::rc::Rc::new(
+                        #yfb::state_model::#state_model::new(
+                            #yfb::modifier::FlattenModifier::<#model_ident, I>::flatten(self),
+                            #mapping,
+                        ),
+                    ))
+                }
+            });
+            binding_fn_defs.push(quote! {
+                fn #binding_ident(&self) -> #yfb::binding::Binding<#ty>;
+            });
+            binding_fns.push(quote! {
+                fn #binding_ident(&self) -> #yfb::binding::Binding<#ty> {
+                    #yfb::binding::Binding::#map(
+                        &#yfb::binding::Binding::flatten::<#model_ident, I>(self),
+                        #mapping,
+                    )
+                }
+            });
+        }
 
         quote! {
-            #vis trait #trait_ident {
+            impl #modifier_ident {
+                #(#modifier_fns)*
+            }
+
+            impl #yfb::model::Flatten<#model_ident, #yfb::model::Here> for #model_ident {
+                fn flatten(
+                    state_model: #yfb::state_model::StateModelRc<Self>,
+                ) -> #yfb::state_model::StateModelRc<#model_ident> {
+                    state_model
+                }
+            }
+
+            impl<M, I> #yfb::modifier::FlattenModifier<M, I> for #modifier_ident
+            where
+                #model_ident: #yfb::model::Flatten<M, I>,
+                M: #yfb::model::ModelState,
+            {
+                fn flatten(&self) -> #yfb::state_model::StateModelRc<M> {
+                    <#model_ident as #yfb::model::Flatten<M, I>>::flatten(
+                        ::std::clone::Clone::clone(#yfb::modifier::Modifier::state_model(self)),
+                    )
+                }
+            }
+
+            #vis trait #modifier_ext_ident<I> {
+                #(#modifier_fn_defs)*
+            }
+
+            impl<T, I> #modifier_ext_ident<I> for T
+            where
+                T: #yfb::modifier::FlattenModifier<#model_ident, I>,
+            {
+                #(#modifier_ext_fns)*
+            }
+
+            #vis trait #binding_ext_ident<I> {
                 #(#binding_fn_defs)*
             }
 
-            impl #trait_ident for #yfb::binding::Binding<#model_ident> {
+            impl<T, I> #binding_ext_ident<I> for #yfb::binding::Binding<T>
+            where
+                T: #yfb::model::Flatten<#model_ident, I>,
+            {
                 #(#binding_fns)*
             }
         }
     }
+
+    /// Embeds a flattened field's model into this one, exposing its accessors and those of the models it flattens in turn
+    fn expand_flatten(&self, field: &ModelField, mapping: &Ident) -> TokenStream {
+        let yfb = &self.path;
+        let model_ident = &self.ident;
+        let ty = &field.ty;
+
+        quote! {
+            impl<M, I> #yfb::model::Flatten<M, #yfb::model::Through<#mapping, I>> for #model_ident
+            where
+                #ty: #yfb::model::Flatten<M, I>,
+                M: #yfb::model::ModelState,
+            {
+                fn flatten(
+                    state_model: #yfb::state_model::StateModelRc<Self>,
+                ) -> #yfb::state_model::StateModelRc<M> {
+                    <#ty as #yfb::model::Flatten<M, I>>::flatten(::std::rc::Rc::new(
+                        #yfb::state_model::MappedStateModel::new(state_model, #mapping),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// A field exposed through the generated modifier and binding methods
+struct Accessor<'a> {
+    ident: Ident,
+    ty: &'a Type,
+    mapping: Ident,
+    /// Whether `mapping` is a `VariantMapping` rather than a `Mapping`
+    variant: bool,
 }
@@ -0,0 +1,694 @@
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Ident, Member};
+
+use crate::{Accessor, ModelField, ModelReceiver, ModelVariant};
+
+/// A field of one of the variants, flattened into the enum's state
+struct VariantField<'a> {
+    variant: &'a ModelVariant,
+    field: &'a ModelField,
+    member: Member,
+    /// Name of the field in the generated state, prefixed with the variant's name
+    state_ident: Ident,
+    /// Identifier the field is bound to when destructuring the variant
+    binding_ident: Ident,
+}
+
+impl<'a> VariantField<'a> {
+    fn collect(variant: &'a ModelVariant) -> Vec<Self> {
+        let prefix = variant.ident.to_string().to_snake_case();
+
+        variant
+            .fields
+            .iter()
+            .filter(|field| !field.skip)
+            .map(|field| VariantField {
+                variant,
+                field,
+                member: field.member(),
+                state_ident: format_ident!("{}_{}", prefix, field.ident_name()),
+                binding_ident: format_ident!("__field_{}", field.index),
+            })
+            .collect()
+    }
+
+    fn name(&self) -> String {
+        format!("{}.{}", self.variant.name(), self.field.name())
+    }
+}
+
+impl ModelReceiver {
+    pub(crate) fn expand_enum(&self, variants: &[ModelVariant], tokens: &mut TokenStream) {
+        if let Some((field, attribute)) =
+            variants
+                .iter()
+                .flat_map(|v| v.fields.iter())
+                .find_map(|f| match f {
+                    f if f.flatten => Some((f, "flatten")),
+                    f => f.validation_attribute().map(|attribute| (f, attribute)),
+                })
+        {
+            tokens.extend(
+                syn::Error::new_spanned(
+                    &field.ty,
+                    format!("`{attribute}` is only supported on struct fields"),
+                )
+                .to_compile_error(),
+            );
+            return;
+        }
+
+        let yfb = &self.path;
+        let vis = &self.vis;
+        let model_ident = &self.ident;
+        let variant_ident = format_ident!("{}Variant", model_ident);
+        let state_ident = format_ident!("{}State", model_ident);
+        let modifier_ident = format_ident!("{}Modifier", model_ident);
+
+        let variant_idents = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let variant_fields = variants
+            .iter()
+            .map(VariantField::collect)
+            .collect::<Vec<_>>();
+        let fields = variant_fields.iter().flatten().collect::<Vec<_>>();
+
+        // Variant `FooBar` with field `x` and variant `Foo` with field `bar_x` would share a state field
+        if let Some((first, second)) = fields.iter().enumerate().find_map(|(index, f)| {
+            fields[..index]
+                .iter()
+                .find(|other| other.state_ident == f.state_ident)
+                .map(|other| (other, f))
+        }) {
+            tokens.extend(
+                syn::Error::new_spanned(
+                    &second.field.ty,
+                    format!(
+                        "`{}::{}` and `{}::{}` both generate the accessor `{}`",
+                        first.variant.ident,
+                        first.field.ident_name(),
+                        second.variant.ident,
+                        second.field.ident_name(),
+                        second.state_ident,
+                    ),
+                )
+                .to_compile_error(),
+            );
+            return;
+        }
+
+        let state_fields = fields.iter().map(|f| {
+            let ident = &f.state_ident;
+            let ty = &f.field.ty;
+            quote! {
+                pub #ident: <#ty as #yfb::model::ModelState>::State
+            }
+        });
+
+        // Skipped fields are included, as they still need to be constructed. Their `Default` bound is reported at the field
+        let defaults = variants.iter().map(|variant| {
+            let (members, values) = variant
+                .fields
+                .iter()
+                .map(|f| {
+                    let ty = &f.ty;
+                    let value = match f.skip {
+                        true => quote_spanned! {ty.span()=>
+                            <#ty as #yfb::model::SkippedDefault>::skipped_default()
+                        },
+                        false => quote!(<#ty as ::std::default::Default>::default()),
+                    };
+                    (f.member(), value)
+                })
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                { #(#members: #values),* }
+            }
+        });
+
+        // Destructures the variant, binding each field to its `binding_ident`
+        let patterns = variant_fields
+            .iter()
+            .zip(&variant_idents)
+            .map(|(fields, variant)| {
+                let (members, bindings) = fields
+                    .iter()
+                    .map(|f| (&f.member, &f.binding_ident))
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                quote! {
+                    #model_ident::#variant { #(#members: #bindings,)* .. }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let create_fields = fields.iter().map(|f| {
+            let ident = &f.state_ident;
+            let ty = &f.field.ty;
+            let binding = &f.binding_ident;
+            let variant = &f.variant.ident;
+            let member = &f.member;
+            quote! {
+                #ident: match model {
+                    #model_ident::#variant { #member: #binding, .. } => {
+                        #yfb::model::State::create(#binding, with_initial, generation.clone())
+                    }
+                    _ => #yfb::model::State::create(
+                        &<#ty as ::std::default::Default>::default(),
+                        false,
+                        generation.clone(),
+                    ),
+                }
+            }
+        });
+
+        let update_fields = variant_fields.iter().map(|fields| {
+            let (idents, bindings) = fields
+                .iter()
+                .map(|f| (&f.state_ident, &f.binding_ident))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                #( #yfb::model::State::update(&mut self.#idents, #bindings, replace); )*
+            }
+        });
+
+        let restore_fields = variant_fields.iter().map(|fields| {
+            let (idents, bindings) = fields
+                .iter()
+                .map(|f| (&f.state_ident, &f.binding_ident))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                #( #yfb::model::State::restore_initial(&self.#idents, #bindings); )*
+            }
+        });
+
+        let dirty_fields = variant_fields.iter().map(|fields| {
+            let idents = fields
+                .iter()
+                .filter(|f| !f.field.no_dirty)
+                .map(|f| &f.state_ident);
+            quote! {
+                false #( | #yfb::model::Dirty::dirty(&self.#idents) )*
+            }
+        });
+
+        let (touched_fields, focused_fields) = variant_fields
+            .iter()
+            .map(|fields| {
+                let idents = fields.iter().map(|f| &f.state_ident).collect::<Vec<_>>();
+                (
+                    quote! {
+                        false #( | #yfb::model::Touched::touched(&self.#idents) )*
+                    },
+                    quote! {
+                        false #( | #yfb::model::Touched::focused(&self.#idents) )*
+                    },
+                )
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let validating_fields = variant_fields.iter().map(|fields| {
+            let idents = fields.iter().map(|f| &f.state_ident);
+            quote! {
+                false #( | #yfb::model::Validating::validating(&self.#idents) )*
+            }
+        });
+
+        // Errors are reported by the field's identifier regardless of renames, only the active variant's fields are shown
+        let set_errors_fields = variant_fields.iter().map(|fields| {
+            let (idents, names) = fields
+                .iter()
+                .map(|f| (&f.state_ident, f.field.ident_name()))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                #(
+                    #yfb::model::SetErrors::set_errors(
+                        &mut self.#idents,
+                        #yfb::validation::field_errors(errors, #names).as_deref(),
+                        all,
+                    );
+                )*
+            }
+        });
+
+        let (field_idents, field_types) = fields
+            .iter()
+            .map(|f| (&f.state_ident, &f.field.ty))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let mappings = fields.iter().map(|f| {
+            let mapping_ident = Self::variant_mapping_ident(model_ident, f);
+            let name = f.name();
+            let ty = &f.field.ty;
+            let variant = &f.variant.ident;
+            let member = &f.member;
+            let binding = &f.binding_ident;
+            let ident = &f.state_ident;
+            quote! {
+                #vis struct #mapping_ident;
+
+                impl #yfb::state_model::VariantMapping for #mapping_ident {
+                    const NAME: &'static ::std::primitive::str = #name;
+                    type From = #model_ident;
+                    type To = #ty;
+
+                    fn variant(&self) -> #variant_ident {
+                        #variant_ident::#variant
+                    }
+
+                    #[allow(unreachable_patterns)]
+                    fn map_model<'a>(&self, model: &'a Self::From) -> ::std::option::Option<&'a Self::To> {
+                        match model {
+                            #model_ident::#variant { #member: #binding, .. } => ::std::option::Option::Some(#binding),
+                            _ => ::std::option::Option::None,
+                        }
+                    }
+
+                    #[allow(unreachable_patterns)]
+                    fn map_model_mut<'a>(&self, model: &'a mut Self::From) -> ::std::option::Option<&'a mut Self::To> {
+                        match model {
+                            #model_ident::#variant { #member: #binding, .. } => ::std::option::Option::Some(#binding),
+                            _ => ::std::option::Option::None,
+                        }
+                    }
+
+                    fn map_state<'a>(
+                        &self,
+                        state: &'a <Self::From as #yfb::model::ModelState>::State,
+                    ) -> &'a <Self::To as #yfb::model::ModelState>::State {
+                        &state.#ident
+                    }
+
+                    fn map_state_mut<'a>(
+                        &self,
+                        state: &'a mut <Self::From as #yfb::model::ModelState>::State,
+                    ) -> &'a mut <Self::To as #yfb::model::ModelState>::State {
+                        &mut state.#ident
+                    }
+                }
+            }
+        });
+
+        let accessors = fields
+            .iter()
+            .map(|f| Accessor {
+                ident: f.state_ident.clone(),
+                ty: &f.field.ty,
+                mapping: Self::variant_mapping_ident(model_ident, f),
+                variant: true,
+            })
+            .collect::<Vec<_>>();
+        let accessors = self.expand_accessors(&accessors, &modifier_ident);
+
+        let variant_names = variants.iter().map(|v| v.name()).collect::<Vec<_>>();
+
+        let visit_fields = variant_fields.iter().map(|fields| {
+            let (names, bindings) = fields
+                .iter()
+                .map(|f| (format!(".{}", f.name()), &f.binding_ident))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                #(
+                    #yfb::model::ModelState::visit_values(
+                        #bindings,
+                        &::std::format!("{}{}", path, #names),
+                        visit,
+                    );
+                )*
+            }
+        });
+
+        let visit_states = variant_fields.iter().map(|fields| {
+            let (names, idents) = fields
+                .iter()
+                .map(|f| (format!(".{}", f.name()), &f.state_ident))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            quote! {
+                #(
+                    #yfb::model::Visit::visit(&self.#idents, &::std::format!("{}{}", path, #names), visitor);
+                )*
+            }
+        });
+
+        let assign_fields = fields.iter().map(|f| {
+            let variant = &f.variant.ident;
+            let member = &f.member;
+            let binding = &f.binding_ident;
+            let name = f.field.name();
+            quote! {
+                (#model_ident::#variant { #member: #binding, .. }, #name) => {
+                    #yfb::model::ModelState::assign_value(#binding, _rest, value)
+                }
+            }
+        });
+
+        let from_path = match variant_idents.first() {
+            Some(variant) => quote! {
+                let mut model = <Self as #yfb::model::EnumModel>::from_variant(#variant_ident::#variant);
+                #yfb::model::ModelState::assign_value(&mut model, path, value)?;
+                ::std::result::Result::Ok(model)
+            },
+            None => quote! {
+                ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath)
+            },
+        };
+
+        let derive_serde = self.derive_serde();
+        let skip_serde = self.skip_serde();
+
+        tokens.extend(quote! {
+            impl #yfb::model::ModelState for #model_ident {
+                type State = #state_ident;
+                type Modifier = #modifier_ident;
+
+                fn visit_values(
+                    &self,
+                    path: &::std::primitive::str,
+                    visit: &mut dyn ::std::ops::FnMut(&::std::primitive::str, #yfb::yew::AttrValue),
+                ) {
+                    match self {
+                        #(
+                            #patterns => {
+                                visit(path, #yfb::yew::AttrValue::Static(#variant_names));
+                                #visit_fields
+                            }
+                        )*
+                    }
+                }
+
+                /// Assigning the variant's name at the path of the enum, or a field of another variant, switches to that variant
+                #[allow(unreachable_patterns)]
+                fn assign_value(
+                    &mut self,
+                    path: &::std::primitive::str,
+                    value: &#yfb::yew::AttrValue,
+                ) -> ::std::result::Result<(), #yfb::model::AssignError> {
+                    let (variant, field) = match path {
+                        "" => (value.as_str(), ::std::option::Option::None),
+                        path => {
+                            let (variant, rest) = #yfb::model::split_field(path)
+                                .ok_or(#yfb::model::AssignError::UnknownPath)?;
+                            (variant, ::std::option::Option::Some(rest))
+                        }
+                    };
+                    let variant = match (variant, field) {
+                        #( (#variant_names, _) => #variant_ident::#variant_idents, )*
+                        (_, ::std::option::Option::None) => {
+                            return ::std::result::Result::Err(#yfb::model::AssignError::InvalidValue(
+                                #yfb::field::parse_error::<Self>(value.clone()),
+                            ))
+                        }
+                        _ => return ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath),
+                    };
+
+                    if #yfb::model::EnumModel::variant(self) != variant {
+                        let mut model = <Self as #yfb::model::EnumModel>::from_variant(variant);
+                        #yfb::model::ModelState::assign_value(&mut model, path, value)?;
+                        *self = model;
+                        return ::std::result::Result::Ok(());
+                    }
+
+                    let ::std::option::Option::Some(field) = field else {
+                        return ::std::result::Result::Ok(());
+                    };
+                    let (field, _rest) = #yfb::model::split_field(field)
+                        .ok_or(#yfb::model::AssignError::UnknownPath)?;
+                    match (self, field) {
+                        #(#assign_fields)*
+                        _ => ::std::result::Result::Err(#yfb::model::AssignError::UnknownPath),
+                    }
+                }
+
+                fn from_path(
+                    path: &::std::primitive::str,
+                    value: &#yfb::yew::AttrValue,
+                ) -> ::std::result::Result<Self, #yfb::model::AssignError> {
+                    #from_path
+                }
+            }
+
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #derive_serde
+            #vis enum #variant_ident {
+                #(#variant_idents),*
+            }
+
+            impl #yfb::model::EnumModel for #model_ident {
+                type Variant = #variant_ident;
+
+                fn variant(&self) -> #variant_ident {
+                    match self {
+                        #( #model_ident::#variant_idents { .. } => #variant_ident::#variant_idents, )*
+                    }
+                }
+
+                fn from_variant(variant: #variant_ident) -> Self {
+                    match variant {
+                        #( #variant_ident::#variant_idents => #model_ident::#variant_idents #defaults, )*
+                    }
+                }
+            }
+
+            #[derive(Debug, PartialEq)]
+            #derive_serde
+            #vis struct #state_ident {
+                __variant: #variant_ident,
+                __initial_variant: ::std::option::Option<#variant_ident>,
+                #skip_serde
+                __generation: ::std::primitive::usize,
+                #skip_serde
+                __generation_handle: #yfb::hooks::UseGenerationHandle,
+                __messages: #yfb::model::Messages,
+                #(#state_fields),*
+            }
+
+            impl #state_ident {
+                /// Returns the variant the state currently tracks
+                #vis fn variant(&self) -> #variant_ident {
+                    self.__variant
+                }
+
+                /// Sets the errors of the active variant's fields and the model's own messages
+                fn set_model_errors(
+                    &mut self,
+                    errors: ::std::option::Option<&#yfb::validation::ValidationErrors>,
+                    all: ::std::primitive::bool,
+                ) {
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => { #set_errors_fields } )*
+                    }
+
+                    let messages = #yfb::validation::schema_messages(errors);
+                    if messages.is_empty() || all || #yfb::model::Dirty::dirty(self) {
+                        self.__messages.set(messages);
+                    }
+                }
+            }
+
+            impl #yfb::model::State<#model_ident> for #state_ident {
+                #[allow(unreachable_patterns)]
+                fn create(model: &#model_ident,
+                          with_initial: ::std::primitive::bool,
+                          generation: #yfb::hooks::UseGenerationHandle) -> Self {
+                    let variant = #yfb::model::EnumModel::variant(model);
+
+                    Self {
+                        __variant: variant,
+                        __initial_variant: with_initial.then_some(variant),
+                        __generation: generation.generation(),
+                        #(#create_fields,)*
+                        __messages: #yfb::model::Messages::new(generation.clone()),
+                        __generation_handle: generation,
+                    }
+                }
+
+                fn update(&mut self, model: &#model_ident, replace: bool) {
+                    let variant = #yfb::model::EnumModel::variant(model);
+
+                    if self.__variant != variant {
+                        self.__variant = variant;
+                        self.__generation = self.__generation_handle.increase();
+                    }
+
+                    if replace && self.__initial_variant != ::std::option::Option::Some(variant) {
+                        self.__initial_variant = ::std::option::Option::Some(variant);
+                        self.__generation = self.__generation_handle.increase();
+                    }
+
+                    match model {
+                        #( #patterns => { #update_fields } )*
+                    }
+                }
+
+                fn attach(&mut self, generation: #yfb::hooks::UseGenerationHandle) {
+                    #(
+                        #yfb::model::State::<#field_types>::attach(&mut self.#field_idents, ::std::clone::Clone::clone(&generation));
+                    )*
+                    self.__messages.attach(::std::clone::Clone::clone(&generation));
+                    self.__generation = generation.generation();
+                    self.__generation_handle = generation;
+                }
+
+                fn restore_initial(&self, model: &mut #model_ident) -> ::std::primitive::bool {
+                    let ::std::option::Option::Some(initial) = self.__initial_variant else {
+                        return false;
+                    };
+                    if #yfb::model::EnumModel::variant(model) != initial {
+                        *model = #yfb::model::EnumModel::from_variant(initial);
+                    }
+
+                    match model {
+                        #( #patterns => { #restore_fields } )*
+                    }
+                    true
+                }
+
+                fn initial_model(&self) -> ::std::option::Option<#model_ident> {
+                    let mut model = #yfb::model::EnumModel::from_variant(self.__initial_variant?);
+                    #yfb::model::State::restore_initial(self, &mut model);
+                    ::std::option::Option::Some(model)
+                }
+
+                fn generation(&self) -> usize {
+                    [
+                        self.__generation,
+                        self.__messages.generation(),
+                        #( #yfb::model::State::<#field_types>::generation(&self.#field_idents), )*
+                    ].into_iter().max().unwrap_or_default()
+                }
+            }
+
+            impl #yfb::model::SetErrors for #state_ident {
+                fn set_errors(
+                    &mut self,
+                    errors: ::std::option::Option<&#yfb::validation::ValidationErrorsKind>,
+                    all: ::std::primitive::bool,
+                ) {
+                    let errors = match errors {
+                        ::std::option::Option::Some(#yfb::validation::ValidationErrorsKind::Struct(errors)) => {
+                            ::std::option::Option::Some(&**errors)
+                        }
+                        _ => ::std::option::Option::None,
+                    };
+                    self.set_model_errors(errors, all);
+                }
+
+                fn clear_errors(&mut self) {
+                    #( #yfb::model::SetErrors::clear_errors(&mut self.#field_idents); )*
+                    self.__messages.set(::std::vec::Vec::new());
+                }
+            }
+
+            impl #yfb::model::ModelMessages for #state_ident {
+                fn messages(&self) -> &#yfb::model::Messages {
+                    &self.__messages
+                }
+
+                fn messages_mut(&mut self) -> &mut #yfb::model::Messages {
+                    &mut self.__messages
+                }
+            }
+
+            impl #yfb::model::Visit for #state_ident {
+                fn visit(&self, path: &::std::primitive::str, visitor: &mut dyn #yfb::model::Visitor) {
+                    let name = |variant| match variant {
+                        #( #variant_ident::#variant_idents => #variant_names, )*
+                    };
+                    visitor.variant(path, self.__initial_variant.map(name), name(self.__variant));
+                    visitor.messages(path, &self.__messages);
+
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => { #visit_states } )*
+                    }
+                }
+            }
+
+            impl #yfb::model::Dirty for #state_ident {
+                fn dirty(&self) -> ::std::primitive::bool {
+                    if self.__initial_variant.is_some_and(|initial| initial != self.__variant) {
+                        return true;
+                    }
+
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => #dirty_fields, )*
+                    }
+                }
+            }
+
+            impl #yfb::model::Touched for #state_ident {
+                fn touched(&self) -> ::std::primitive::bool {
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => #touched_fields, )*
+                    }
+                }
+
+                fn focused(&self) -> ::std::primitive::bool {
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => #focused_fields, )*
+                    }
+                }
+
+                fn touch(&mut self) {
+                    #( #yfb::model::Touched::touch(&mut self.#field_idents); )*
+                }
+            }
+
+            impl #yfb::model::Validating for #state_ident {
+                fn validating(&self) -> ::std::primitive::bool {
+                    match self.__variant {
+                        #( #variant_ident::#variant_idents => #validating_fields, )*
+                    }
+                }
+            }
+
+            #vis struct #modifier_ident(#yfb::modifier::BaseModifier<#model_ident>, ::std::primitive::usize);
+
+            impl #yfb::modifier::Modifier<#model_ident> for #modifier_ident {
+                fn create(state_model: #yfb::state_model::StateModelRc<#model_ident>) -> Self {
+                    let generation = #yfb::model::State::<#model_ident>::generation(
+                        &*#yfb::state_model::StateModel::state(&*state_model),
+                    );
+                    Self(#yfb::modifier::Modifier::create(state_model), generation)
+                }
+
+                fn state_model(&self) -> &#yfb::state_model::StateModelRc<#model_ident> {
+                    #yfb::modifier::Modifier::state_model(&self.0)
+                }
+
+                fn validate(&self, all: ::std::primitive::bool) -> ::std::result::Result<(), #yfb::validation::ValidationErrors> {
+                    let validation = {
+                        let state_model = #yfb::modifier::Modifier::state_model(&self.0);
+                        #yfb::model::Validator::validate(&*state_model.model())
+                    };
+
+                    let (_, mut state) = #yfb::modifier::Modifier::state_model(&self.0).as_mut();
+                    state.set_model_errors(validation.as_ref().err(), all);
+                    validation
+                }
+            }
+
+            // Like the modifiers of structs, validates when the state changed while the modifier was alive, e.g. by switching the variant
+            impl Drop for #modifier_ident {
+                fn drop(&mut self) {
+                    let changed = #yfb::model::State::<#model_ident>::generation(&*#yfb::modifier::Modifier::state(self)) != self.1;
+                    if changed || #yfb::modifier::Modifier::dirty(self) {
+                        let _ = #yfb::modifier::Modifier::validate(self, false);
+                    }
+                }
+            }
+
+            impl #yfb::modifier::VariantModifier<#model_ident> for #modifier_ident {}
+
+            #(#mappings)*
+            #accessors
+        });
+    }
+
+    fn variant_mapping_ident(model_ident: &Ident, field: &VariantField) -> Ident {
+        format_ident!(
+            "{}{}Mapping",
+            model_ident,
+            field.state_ident.to_string().to_pascal_case()
+        )
+    }
+}